@@ -0,0 +1,57 @@
+//! Batch kernels over `MultiVec3` columns against the same math over `Vec<Vec3>`
+
+use std::hint::black_box;
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use amy_math::{batch, containers::multi_vec::multi_vec3::MultiVec3, prelude::*};
+
+const ROWS: usize = 100_000;
+
+fn aos() -> Vec<Vec3> {
+    (0..ROWS).map(|i| {
+        let t = i as f32 * 0.37;
+        Vec3::new(t.sin() * 5.0, t.cos() - 0.3, t.fract() + 0.01)
+    }).collect()
+}
+
+fn soa() -> MultiVec3<f32, f32, f32> {
+    aos().into_iter().map(|v| (v[0], v[1], v[2])).collect()
+}
+
+fn bench_in_place(c: &mut Criterion, name: &str, aos_op: impl Fn(&mut [Vec3]), soa_op: impl Fn([&mut [f32]; 3])) {
+    let mut group = c.benchmark_group(name);
+    group.bench_function("aos", |b| b.iter_batched_ref(aos, |v| aos_op(v), BatchSize::LargeInput));
+    group.bench_function("soa", |b| b.iter_batched_ref(soa, |v| { let (x, y, z) = v.cols_mut(); soa_op([x, y, z]) }, BatchSize::LargeInput));
+    group.finish();
+}
+
+fn bench_reduce(c: &mut Criterion, name: &str, aos_op: impl Fn(&[Vec3], &mut [f32]), soa_op: impl Fn([&[f32]; 3], &mut [f32])) {
+    let (aos, soa) = (aos(), soa());
+    let mut out = vec![0.0; ROWS];
+    let mut group = c.benchmark_group(name);
+    group.bench_function("aos", |b| b.iter(|| {
+        aos_op(&aos, &mut out);
+        black_box(&mut out);
+    }));
+    group.bench_function("soa", |b| b.iter(|| {
+        soa_op(soa.cols().into(), &mut out);
+        black_box(&mut out);
+    }));
+    group.finish();
+}
+
+fn benches(c: &mut Criterion) {
+    // the second operand of `add` and `dot` is another set of vectors in the same layout
+    let (other_aos, other_soa) = (aos(), soa());
+    let (ox, oy, oz) = other_soa.cols();
+    let other = [ox, oy, oz];
+    let each = |f: fn(&mut Vec3)| move |v: &mut [Vec3]| v.iter_mut().for_each(f);
+    bench_in_place(c, "add", |v| v.iter_mut().zip(&other_aos).for_each(|(v, o)| *v += o), |v| batch::add(v, other));
+    bench_in_place(c, "scale", each(|v| *v *= 1.5), |v| batch::scale(v, 1.5));
+    bench_in_place(c, "normalize", each(|v| *v = v.norm()), batch::normalize);
+    bench_in_place(c, "qnormalize", each(|v| *v = v.qnorm()), batch::qnormalize);
+    bench_reduce(c, "dot", |v, out| out.iter_mut().zip(v.iter().zip(&other_aos)).for_each(|(out, (v, o))| *out = v.dot(o)), |v, out| batch::dot(v, other, out));
+    bench_reduce(c, "len", |v, out| out.iter_mut().zip(v).for_each(|(out, v)| *out = v.len()), batch::len);
+}
+
+criterion_group!(batch_benches, benches);
+criterion_main!(batch_benches);
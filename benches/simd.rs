@@ -0,0 +1,69 @@
+//! Component-wise `Vec3`/`Vec4` arithmetic through the generic operators against hand-written SSE,
+//! to check that the operators don't need kernels of their own
+
+use std::{arch::x86_64::*, hint::black_box};
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use amy_math::prelude::*;
+
+const COUNT: usize = 10_000;
+
+fn vec4s() -> Vec<Vec4> {
+    (0..COUNT).map(|i| {
+        let t = i as f32 * 0.37;
+        Vec4::new(t.sin() * 5.0, t.cos() - 0.3, t.fract() + 0.01, t * 1e-3)
+    }).collect()
+}
+
+fn vec3s() -> Vec<Vec3> {
+    vec4s().into_iter().map(|v| Vec3::new(v[0], v[1], v[2])).collect()
+}
+
+/// Load `N` floats into the low lanes, like `simd.rs` does
+#[inline]
+fn load<const N: usize>(a: &[f32; N]) -> __m128 {
+    let mut padded = [0.0; 4];
+    padded[..N].copy_from_slice(a);
+    unsafe { _mm_loadu_ps(padded.as_ptr()) }
+}
+
+#[inline]
+fn store<const N: usize>(v: __m128, out: &mut [f32; N]) {
+    let mut padded = [0.0; 4];
+    unsafe { _mm_storeu_ps(padded.as_mut_ptr(), v) };
+    out.copy_from_slice(&padded[..N]);
+}
+
+fn bench_op<const N: usize>(
+    c: &mut Criterion,
+    name: &str,
+    data: fn() -> Vec<Vector<f32, N>>,
+    op: impl Fn(Vector<f32, N>, Vector<f32, N>) -> Vector<f32, N>,
+    packed: impl Fn(__m128, __m128) -> __m128,
+) {
+    let other = data();
+    let mut group = c.benchmark_group(name);
+    group.bench_function("operator", |b| b.iter_batched_ref(data, |v| {
+        v.iter_mut().zip(&other).for_each(|(v, &o)| *v = op(*v, o));
+        black_box(v);
+    }, BatchSize::LargeInput));
+    group.bench_function("sse", |b| b.iter_batched_ref(data, |v| {
+        v.iter_mut().zip(&other).for_each(|(v, o)| {
+            let r = packed(load(v.as_array()), load(o.as_array()));
+            store(r, v.as_array_mut());
+        });
+        black_box(v);
+    }, BatchSize::LargeInput));
+    group.finish();
+}
+
+fn benches(c: &mut Criterion) {
+    // SAFETY: SSE is part of the x86_64 baseline
+    bench_op(c, "vec4_add", vec4s, |a, b| a + b, |a, b| unsafe { _mm_add_ps(a, b) });
+    bench_op(c, "vec4_mul", vec4s, |a, b| a * b, |a, b| unsafe { _mm_mul_ps(a, b) });
+    bench_op(c, "vec4_scale", vec4s, |a, _| a * 1.5, |a, _| unsafe { _mm_mul_ps(a, _mm_set1_ps(1.5)) });
+    bench_op(c, "vec3_add", vec3s, |a, b| a + b, |a, b| unsafe { _mm_add_ps(a, b) });
+    bench_op(c, "vec3_mul", vec3s, |a, b| a * b, |a, b| unsafe { _mm_mul_ps(a, b) });
+}
+
+criterion_group!(simd_benches, benches);
+criterion_main!(simd_benches);
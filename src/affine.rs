@@ -0,0 +1,240 @@
+//! Affine transforms: a linear part (rotation, scale, shear) followed by a translation
+//!
+//! Points and directions transform differently, so there is no `Affine * Vector`:
+//! [`Affine::transform_point`] applies the translation, [`Affine::transform_vector`] does not.
+
+use std::ops::*;
+use crate::{num::*, vec::*, mat::*, quat::*, angle::*};
+
+/// `x ↦ linear * x + translation`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Affine<T, const N: usize> {
+    pub linear: Matrix<T, N, N>,
+    pub translation: Vector<T, N>,
+}
+
+pub type Affine2<T> = Affine<T, 2>;
+pub type Affine3<T> = Affine<T, 3>;
+
+/// Composition; `a * b` applies `b` first, then `a`
+impl<T: Num, const N: usize> Mul for Affine<T, N> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self { linear: self.linear * rhs.linear, translation: self.linear * rhs.translation + self.translation }
+    }
+}
+impl<T: Num, const N: usize> Mul for &Affine<T, N> { type Output = Affine<T, N>; fn mul(self, rhs: Self) -> Self::Output { *self * *rhs } }
+impl<T: Num, const N: usize> MulAssign for Affine<T, N> { fn mul_assign(&mut self, rhs: Self) { *self = *self * rhs; } }
+
+impl<T: Num, const N: usize> Affine<T, N> {
+    pub fn new(linear: Matrix<T, N, N>, translation: Vector<T, N>) -> Self { Self { linear, translation } }
+    pub fn identity() -> Self { Self::from_linear(Matrix::identity()) }
+    pub fn from_linear(linear: Matrix<T, N, N>) -> Self { Self { linear, translation: Vector::zero() } }
+    pub fn from_translation(translation: Vector<T, N>) -> Self { Self { linear: Matrix::identity(), translation } }
+    /// Scale along each axis
+    pub fn from_scale(scale: Vector<T, N>) -> Self {
+        Self::from_linear(Matrix(std::array::from_fn(#[inline] |i| {
+            let mut row = Vector::zero();
+            row[i] = scale[i];
+            row
+        })))
+    }
+
+    /// Rotate, scale and shear, then translate
+    #[must_use]
+    pub fn transform_point(&self, point: Vector<T, N>) -> Vector<T, N> { self.linear * point + self.translation }
+    /// Rotate, scale and shear only; for directions and offsets
+    #[must_use]
+    pub fn transform_vector(&self, vector: Vector<T, N>) -> Vector<T, N> { self.linear * vector }
+}
+
+// 2D
+impl<T: Num> Affine<T, 2> {
+    /// `x += x_by_y * y` and `y += y_by_x * x`
+    pub fn from_shear(x_by_y: T, y_by_x: T) -> Self { Self::from_linear(Matrix::<T, 2, 2>::new(T::one(), x_by_y, y_by_x, T::one())) }
+
+    /// Homogeneous matrix, for column vectors `(x, y, 1)`
+    pub fn to_mat3(&self) -> Matrix<T, 3, 3> {
+        let [Vector([a, b]), Vector([c, d])] = self.linear.0;
+        let Vector([x, y]) = self.translation;
+        Matrix::<T, 3, 3>::new(a, b, x, c, d, y, T::zero(), T::zero(), T::one())
+    }
+    /// The inverse of [`Self::to_mat3`]; the bottom row is assumed to be `(0, 0, 1)`
+    pub fn from_mat3(m: &Matrix<T, 3, 3>) -> Self {
+        let [Vector([a, b, x]), Vector([c, d, y]), _] = m.0;
+        Self::new(Matrix::<T, 2, 2>::new(a, b, c, d), Vector([x, y]))
+    }
+}
+impl<T: Real> Affine<T, 2> {
+    /// [`None`] if the linear part is singular
+    #[must_use]
+    pub fn inverse(&self) -> Option<Self> {
+        let linear = self.linear.inverse()?;
+        Some(Self { linear, translation: -(linear * self.translation) })
+    }
+}
+impl<T: Real> Affine<T, 2> {
+    /// Counter-clockwise rotation by `angle`
+    pub fn from_angle(angle: impl Into<Radians<T>>) -> Self {
+        let (s, c) = angle.into().sin_cos();
+        Self::from_linear(Matrix::<T, 2, 2>::new(c, -s, s, c))
+    }
+    /// Scale, then rotate by `angle`, then translate
+    pub fn from_scale_angle_translation(scale: Vector<T, 2>, angle: impl Into<Radians<T>>, translation: Vector<T, 2>) -> Self {
+        Self { translation, ..Self::from_angle(angle) * Self::from_scale(scale) }
+    }
+    /// The inverse of [`Self::from_scale_angle_translation`], exact if there is no shear.
+    /// A reflection is returned as a negative x scale.
+    pub fn to_scale_angle_translation(&self) -> (Vector<T, 2>, Radians<T>, Vector<T, 2>) {
+        let (x_axis, y_axis) = (self.linear.col(0), self.linear.col(1));
+        let sign = if self.linear.determinant() < T::zero() { -T::one() } else { T::one() };
+        let scale = Vector([x_axis.len() * sign, y_axis.len()]);
+        let angle = Radians((x_axis[1] * sign).atan2(x_axis[0] * sign));
+        (scale, angle, self.translation)
+    }
+}
+
+// 3D
+impl<T: Num> Affine<T, 3> {
+    /// `x += xy * y + xz * z`, `y += yx * x + yz * z` and `z += zx * x + zy * y`
+    pub fn from_shear(xy: T, xz: T, yx: T, yz: T, zx: T, zy: T) -> Self {
+        let one = T::one();
+        Self::from_linear(Matrix::<T, 3, 3>::new(one, xy, xz, yx, one, yz, zx, zy, one))
+    }
+
+    /// Homogeneous matrix, for column vectors `(x, y, z, 1)`
+    pub fn to_mat4(&self) -> Matrix<T, 4, 4> {
+        let [Vector([a, b, c]), Vector([d, e, f]), Vector([g, h, i])] = self.linear.0;
+        let Vector([x, y, z]) = self.translation;
+        let (zero, one) = (T::zero(), T::one());
+        Matrix::<T, 4, 4>::new(a, b, c, x, d, e, f, y, g, h, i, z, zero, zero, zero, one)
+    }
+    /// The inverse of [`Self::to_mat4`]; the bottom row is assumed to be `(0, 0, 0, 1)`
+    pub fn from_mat4(m: &Matrix<T, 4, 4>) -> Self {
+        let [Vector([a, b, c, x]), Vector([d, e, f, y]), Vector([g, h, i, z]), _] = m.0;
+        Self::new(Matrix::<T, 3, 3>::new(a, b, c, d, e, f, g, h, i), Vector([x, y, z]))
+    }
+}
+impl<T: Real> Affine<T, 3> {
+    /// [`None`] if the linear part is singular
+    #[must_use]
+    pub fn inverse(&self) -> Option<Self> {
+        let linear = self.linear.inverse()?;
+        Some(Self { linear, translation: -(linear * self.translation) })
+    }
+}
+
+impl<T: Num> From<Affine<T, 2>> for Matrix<T, 3, 3> { fn from(value: Affine<T, 2>) -> Self { value.to_mat3() } }
+impl<T: Num> From<&Matrix<T, 3, 3>> for Affine<T, 2> { fn from(value: &Matrix<T, 3, 3>) -> Self { Self::from_mat3(value) } }
+impl<T: Num> From<Affine<T, 3>> for Matrix<T, 4, 4> { fn from(value: Affine<T, 3>) -> Self { value.to_mat4() } }
+impl<T: Num> From<&Matrix<T, 4, 4>> for Affine<T, 3> { fn from(value: &Matrix<T, 4, 4>) -> Self { Self::from_mat4(value) } }
+
+impl<T: Real> Affine<T, 3> {
+    /// Rotation by a unit quaternion
+    pub fn from_quat(rotation: Quat<T>) -> Self { Self::from_linear(rotation.to_mat3()) }
+
+    /// Rotation by `angle` about a unit length `axis`
+    pub fn from_axis_angle(axis: Vector<T, 3>, angle: impl Into<Radians<T>>) -> Self { Self::from_quat(Quat::from_axis_angle(axis, angle)) }
+
+    /// Scale, then rotate, then translate
+    pub fn from_scale_rotation_translation(scale: Vector<T, 3>, rotation: Quat<T>, translation: Vector<T, 3>) -> Self {
+        Self { translation, ..Self::from_quat(rotation) * Self::from_scale(scale) }
+    }
+
+    /// The inverse of [`Self::from_scale_rotation_translation`], exact if there is no shear.
+    /// A reflection is returned as a negative x scale.
+    pub fn to_scale_rotation_translation(&self) -> (Vector<T, 3>, Quat<T>, Vector<T, 3>) {
+        let sign = if self.linear.determinant() < T::zero() { -T::one() } else { T::one() };
+        let scale = Vector([self.linear.col(0).len() * sign, self.linear.col(1).len(), self.linear.col(2).len()]);
+        let rotation = Matrix::<T, 3, 3>::from_cols(std::array::from_fn(|i| self.linear.col(i) / scale[i]));
+        (scale, Quat::from_mat3(&rotation).norm(), self.translation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_approx_eq, approx_eq::ApproxEq};
+    use std::f64::consts::FRAC_PI_2;
+
+    #[test]
+    fn test_point_vector() {
+        let t = Affine2::from_translation(IVec2::new(5, -1)) * Affine2::from_scale(IVec2::new(2, 3));
+        assert_eq!(t.transform_point(IVec2::new(1, 1)), IVec2::new(7, 2));
+        assert_eq!(t.transform_vector(IVec2::new(1, 1)), IVec2::new(2, 3));
+
+        let shear = Affine2::from_shear(2, 0);
+        assert_eq!(shear.transform_point(IVec2::new(1, 3)), IVec2::new(7, 3));
+        let shear = Affine3::from_shear(1, 0, 0, 0, 0, 2);
+        assert_eq!(shear.transform_vector(IVec3::new(1, 2, 3)), IVec3::new(3, 2, 7));
+    }
+
+    #[test]
+    fn test_compose_inverse() {
+        let a = Affine3::<f64>::from_scale_rotation_translation(
+            DVec3::new(2.0, 0.5, 3.0),
+            Quat::<f64>::from_axis_angle(DVec3::new(0.0, 0.6, 0.8), Radians(1.1)),
+            DVec3::new(1.0, -2.0, 4.0),
+        );
+        let b = Affine3::from_shear(0.3, 0.0, -0.2, 0.1, 0.0, 0.4) * Affine3::from_translation(DVec3::new(-3.0, 0.0, 1.0));
+        let p = DVec3::new(0.7, -1.3, 2.9);
+        assert_approx_eq!((a * b).transform_point(p), a.transform_point(b.transform_point(p)), epsilon = 1e-12);
+        assert_approx_eq!((a * b).transform_vector(p), a.transform_vector(b.transform_vector(p)), epsilon = 1e-12);
+        assert_approx_eq!(a.inverse().unwrap().transform_point(a.transform_point(p)), p, epsilon = 1e-12);
+        assert_approx_eq!(a * a.inverse().unwrap(), Affine3::identity(), epsilon = 1e-12);
+        assert_eq!(Affine3::from_scale(DVec3::new(1.0, 0.0, 1.0)).inverse(), None);
+
+        let r = Affine2::from_angle(Radians(FRAC_PI_2)) * Affine2::from_translation(DVec2::new(1.0, 0.0));
+        assert_approx_eq!(r.transform_point(DVec2::zero()), DVec2::new(0.0, 1.0), epsilon = 1e-15);
+        assert_approx_eq!(r.inverse().unwrap().transform_point(DVec2::new(0.0, 1.0)), DVec2::zero(), epsilon = 1e-15);
+    }
+
+    #[test]
+    fn test_decompose() {
+        let (scale, angle, translation) = (DVec2::new(-2.0, 0.5), Degrees(150.0), DVec2::new(3.0, 1.0));
+        let (s, a, t) = Affine2::from_scale_angle_translation(scale, angle, translation).to_scale_angle_translation();
+        assert_approx_eq!(s, scale, epsilon = 1e-12);
+        assert_approx_eq!(a.to_degrees().0, angle.0, epsilon = 1e-12);
+        assert_eq!(t, translation);
+
+        let (scale, rotation, translation) = (DVec3::new(1.5, 2.0, 0.25), Quat::<f64>::from_euler(Radians(0.3), Radians(-0.7), Radians(1.1)), DVec3::new(-1.0, 0.0, 9.0));
+        let (s, r, t) = Affine3::<f64>::from_scale_rotation_translation(scale, rotation, translation).to_scale_rotation_translation();
+        assert_approx_eq!(s, scale, epsilon = 1e-12);
+        assert!(r.abs_diff_eq(&rotation, 1e-12) || r.abs_diff_eq(&-rotation, 1e-12), "{r:?}");
+        assert_eq!(t, translation);
+
+        // a reflection comes back as a negative x scale
+        let mirrored = Affine3::from_scale(Vec3::new(1.0, -1.0, 1.0));
+        let (s, r, _) = mirrored.to_scale_rotation_translation();
+        assert_approx_eq!(Affine3::<f32>::from_scale_rotation_translation(s, r, Vec3::zero()), mirrored, epsilon = 1e-6);
+
+        type F = crate::fixed::Fixed<16>;
+        let f = F::from_f64;
+        let (scale, translation) = (Vector::<F, 3>::new(f(2.0), f(0.5), f(1.0)), Vector::<F, 3>::new(f(1.0), f(-3.0), f(0.0)));
+        let rotation = Quat::from_axis_angle(Vector::<F, 3>::new(F::zero(), F::zero(), F::one()), Degrees(f(90.0)));
+        let (s, r, t) = Affine3::from_scale_rotation_translation(scale, rotation, translation).to_scale_rotation_translation();
+        assert_approx_eq!(s, scale, epsilon = f(1e-3));
+        assert_approx_eq!(r, rotation, epsilon = f(1e-3));
+        assert_eq!(t, translation);
+    }
+
+    #[test]
+    fn test_homogeneous() {
+        let a = Affine2::from_scale_angle_translation(DVec2::new(2.0, 3.0), Radians(0.4), DVec2::new(5.0, -1.0));
+        let m = a.to_mat3();
+        let p = DVec2::new(0.5, 2.0);
+        let Vector([x, y, w]) = m * DVec3::new(p[0], p[1], 1.0);
+        assert_eq!((DVec2::new(x, y), w), (a.transform_point(p), 1.0));
+        let Vector([x, y, w]) = m * DVec3::new(p[0], p[1], 0.0);
+        assert_eq!((DVec2::new(x, y), w), (a.transform_vector(p), 0.0));
+        assert_eq!(Affine2::from(&m), a);
+
+        let a = Affine3::<f32>::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), Radians(0.8)) * Affine3::from_translation(Vec3::new(1.0, 2.0, 3.0));
+        let m = Mat4::from(a);
+        assert_eq!(*m.row(3), Vec4::new(0.0, 0.0, 0.0, 1.0));
+        let Vector([x, y, z]) = a.transform_point(Vec3::new(1.0, -1.0, 2.0));
+        assert_approx_eq!(m * Vec4::new(1.0, -1.0, 2.0, 1.0), Vec4::new(x, y, z, 1.0), epsilon = 1e-6);
+        assert_eq!(Affine3::from(&m), a);
+        assert_approx_eq!(Affine3::from(&(m * m)), a * a, epsilon = 1e-6);
+    }
+}
@@ -0,0 +1,195 @@
+//! Angles with their unit in the type, and angles between [`Vector`]s
+//!
+//! Functions taking an angle accept `impl Into<Radians<T>>`, so [`Degrees`] can be passed directly
+//! and a bare number cannot.
+
+use std::{fmt, ops::*};
+use crate::{num::*, vec::*, interp::small};
+
+/// An angle in radians
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct Radians<T>(pub T);
+
+/// An angle in degrees
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct Degrees<T>(pub T);
+
+macro_rules! impl_angle {
+    ($($Angle:ident $unit:literal),+) => {
+        $(
+        impl<T: Copy + Neg<Output = T>> Neg for $Angle<T> { type Output = Self; fn neg(self) -> Self::Output { Self(-self.0) } }
+        impl<T: Copy + Add<Output = T>> Add for $Angle<T> { type Output = Self; fn add(self, rhs: Self) -> Self::Output { Self(self.0 + rhs.0) } }
+        impl<T: Copy + Sub<Output = T>> Sub for $Angle<T> { type Output = Self; fn sub(self, rhs: Self) -> Self::Output { Self(self.0 - rhs.0) } }
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $Angle<T> { type Output = Self; fn mul(self, rhs: T) -> Self::Output { Self(self.0 * rhs) } }
+        impl<T: Copy + Div<Output = T>> Div<T> for $Angle<T> { type Output = Self; fn div(self, rhs: T) -> Self::Output { Self(self.0 / rhs) } }
+        impl<T: AddAssign> AddAssign for $Angle<T> { fn add_assign(&mut self, rhs: Self) { self.0 += rhs.0; } }
+        impl<T: SubAssign> SubAssign for $Angle<T> { fn sub_assign(&mut self, rhs: Self) { self.0 -= rhs.0; } }
+        impl<T: Num> std::iter::Sum for $Angle<T> { fn sum<I: Iterator<Item = Self>>(iter: I) -> Self { Self(iter.fold(T::zero(), #[inline] |acc, angle| acc + angle.0)) } }
+
+        impl<T: fmt::Display> fmt::Display for $Angle<T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt(f)?;
+                f.write_str($unit)
+            }
+        }
+        )+
+    };
+}
+impl_angle!(Radians " rad", Degrees "°");
+
+/// `angle` wrapped into `(-half_turn, half_turn]`
+#[inline]
+fn wrap<T: Real>(angle: T, half_turn: T) -> T {
+    let turn = half_turn + half_turn;
+    let wrapped = angle - turn * ((angle - half_turn) / turn).ceil();
+    // rounding can land exactly on the excluded end
+    if wrapped <= -half_turn { wrapped + turn } else { wrapped }
+}
+
+impl<T: Real> Radians<T> {
+    #[must_use]
+    pub fn to_degrees(self) -> Degrees<T> { Degrees(self.0 * small::<T>(180) / T::PI) }
+    /// The same direction, in `(-π, π]`
+    #[must_use]
+    pub fn wrapped(self) -> Self { Self(wrap(self.0, T::PI)) }
+
+    pub fn sin(self) -> T { self.0.sin() }
+    pub fn cos(self) -> T { self.0.cos() }
+    pub fn tan(self) -> T { self.0.tan() }
+    pub fn sin_cos(self) -> (T, T) { self.0.sin_cos() }
+}
+impl<T: Real> Degrees<T> {
+    #[must_use]
+    pub fn to_radians(self) -> Radians<T> { Radians(self.0 * T::PI / small::<T>(180)) }
+    /// The same direction, in `(-180°, 180°]`
+    #[must_use]
+    pub fn wrapped(self) -> Self { Self(wrap(self.0, small(180))) }
+}
+
+impl<T: Real> From<Degrees<T>> for Radians<T> { fn from(value: Degrees<T>) -> Self { value.to_radians() } }
+impl<T: Real> From<Radians<T>> for Degrees<T> { fn from(value: Radians<T>) -> Self { value.to_degrees() } }
+
+/// Unsigned angle between two vectors, in `[0, π]`
+pub trait AngleBetween {
+    type Output;
+    #[must_use]
+    fn angle_between(self, other: Self) -> Self::Output;
+}
+impl<T: Real, const N: usize> AngleBetween for Vector<T, N> {
+    type Output = Radians<T>;
+    #[inline]
+    fn angle_between(self, other: Self) -> Self::Output {
+        // Kahan's formula, accurate for nearly parallel and nearly opposite vectors alike
+        let (a, b) = (self * other.len(), other * self.len());
+        Radians(small::<T>(2) * (a - b).len().atan2((a + b).len()))
+    }
+}
+impl<T: Real, const N: usize> AngleBetween for &Vector<T, N> {
+    type Output = Radians<T>;
+    #[inline]
+    fn angle_between(self, other: Self) -> Self::Output { (*self).angle_between(*other) }
+}
+
+impl<T: Real> Vector<T, 2> {
+    /// Unit vector at `angle` counter-clockwise from +X
+    pub fn from_angle(angle: impl Into<Radians<T>>) -> Self {
+        let (s, c) = angle.into().sin_cos();
+        Self([c, s])
+    }
+    /// Counter-clockwise angle from +X, in `(-π, π]`
+    #[must_use]
+    pub fn to_angle(self) -> Radians<T> { Radians(self.0[1].atan2(self.0[0])) }
+    /// Angle from `self` to `other`, in `(-π, π]`, positive if counter-clockwise
+    #[must_use]
+    pub fn signed_angle(self, other: Self) -> Radians<T> { Radians(self.cross(other).atan2(self.dot(other))) }
+    /// Rotate counter-clockwise by `angle`
+    #[must_use]
+    pub fn rotate(self, angle: impl Into<Radians<T>>) -> Self {
+        let (s, c) = angle.into().sin_cos();
+        let Vector([x, y]) = self;
+        Self([x * c - y * s, x * s + y * c])
+    }
+}
+impl<T: Real> Vector<T, 3> {
+    /// Angle from `self` to `other`, in `(-π, π]`, positive if counter-clockwise looking down
+    /// `axis` (right-handed about it). The vectors are assumed perpendicular to `axis`; otherwise
+    /// this is the angle between their projections onto the plane perpendicular to it.
+    #[must_use]
+    pub fn signed_angle(self, other: Self, axis: Self) -> Radians<T> {
+        let axis = axis.norm();
+        let (a, b) = (self - axis * self.dot(axis), other - axis * other.dot(axis));
+        Radians(a.cross(b).dot(axis).atan2(a.dot(b)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use std::f64::consts::{PI, FRAC_PI_2, FRAC_PI_4};
+
+    #[test]
+    fn test_conversions() {
+        assert_approx_eq!(Radians::from(Degrees(90.0)).0, FRAC_PI_2);
+        assert_approx_eq!(Degrees::from(Radians(PI)).0, 180.0);
+        assert_approx_eq!(Degrees(-30.0f32).to_radians().to_degrees().0, -30.0);
+        assert_eq!(Radians(1.0) + Radians(0.5) * 2.0 - Radians(0.25), Radians(1.75));
+        assert_eq!(-Degrees(45.0) / 3.0, Degrees(-15.0));
+        assert_eq!([Degrees(10.0), Degrees(20.0)].into_iter().sum::<Degrees<f64>>(), Degrees(30.0));
+        assert!(Degrees(10.0) < Degrees(20.0));
+        assert_eq!(format!("{:.1}", Degrees(12.34)), "12.3°");
+        assert_eq!(Radians(0.5).to_string(), "0.5 rad");
+    }
+
+    #[test]
+    fn test_wrapped() {
+        for (angle, expected) in [(0.0, 0.0), (PI, PI), (-PI, PI), (3.0 * PI, PI), (-3.0 * PI, PI), (1.5 * PI, -FRAC_PI_2), (-1.5 * PI, FRAC_PI_2), (10.0, 10.0 - 4.0 * PI)] {
+            assert_approx_eq!(Radians(angle).wrapped().0, expected, epsilon = 1e-12);
+        }
+        for (angle, expected) in [(180.0, 180.0), (-180.0, 180.0), (540.0, 180.0), (190.0, -170.0), (-725.0, -5.0), (359.0, -1.0)] {
+            assert_eq!(Degrees(angle).wrapped(), Degrees(expected));
+        }
+        for i in -1000..1000 {
+            let wrapped = Radians(i as f32 * 0.1).wrapped().0;
+            assert!(wrapped > -std::f32::consts::PI && wrapped <= std::f32::consts::PI, "{i}");
+        }
+    }
+
+    #[test]
+    fn test_angle_between() {
+        let x = DVec3::new(1.0, 0.0, 0.0);
+        assert_approx_eq!(x.angle_between(DVec3::new(0.0, 3.0, 0.0)).0, FRAC_PI_2);
+        assert_approx_eq!(x.angle_between(DVec3::new(-2.0, 0.0, 0.0)).0, PI);
+        assert_eq!(x.angle_between(x * 5.0).0, 0.0);
+        assert_approx_eq!((&DVec2::new(1.0, 1.0)).angle_between(&DVec2::new(0.0, 1.0)).0, FRAC_PI_4);
+        // acos(dot) would round this to zero
+        assert_approx_eq!(x.angle_between(DVec3::new(1.0, 1e-9, 0.0)).0, 1e-9, relative = 1e-6);
+    }
+
+    #[test]
+    fn test_vec2() {
+        assert_approx_eq!(DVec2::from_angle(Degrees(90.0)), DVec2::new(0.0, 1.0));
+        assert_approx_eq!(DVec2::from_angle(Radians(PI)), DVec2::new(-1.0, 0.0));
+        assert_approx_eq!(DVec2::new(-1.0, -1.0).to_angle().to_degrees().0, -135.0);
+        assert_approx_eq!(DVec2::new(-1.0, 0.0).to_angle().0, PI);
+
+        let v = DVec2::new(2.0, 1.0);
+        assert_approx_eq!(v.rotate(Degrees(90.0)), DVec2::new(-1.0, 2.0));
+        assert_approx_eq!(v.rotate(Radians(-FRAC_PI_2)), DVec2::new(1.0, -2.0));
+        for angle in [-3.0, -1.0, 0.5, 2.9] {
+            assert_approx_eq!(v.signed_angle(v.rotate(Radians(angle))).0, angle, epsilon = 1e-12);
+            assert_approx_eq!(v.rotate(Radians(angle)).to_angle().0, Radians(v.to_angle().0 + angle).wrapped().0, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_signed_angle_3d() {
+        let (x, y, z) = (DVec3::new(1.0, 0.0, 0.0), DVec3::new(0.0, 1.0, 0.0), DVec3::new(0.0, 0.0, 1.0));
+        assert_approx_eq!(x.signed_angle(y, z).0, FRAC_PI_2);
+        assert_approx_eq!(x.signed_angle(y, -z * 4.0).0, -FRAC_PI_2);
+        assert_approx_eq!(y.signed_angle(-y, x).0, PI);
+        // only the part perpendicular to the axis counts
+        assert_approx_eq!(x.signed_angle(y + z * 7.0, z).0, FRAC_PI_2);
+        assert_approx_eq!((x + z).signed_angle(x - y, z).0, -FRAC_PI_4);
+    }
+}
@@ -0,0 +1,239 @@
+//! Approximate equality for floating point results
+
+use crate::{fixed::*, vec::*, mat::*, quat::*, affine::*};
+
+/// Equality within a tolerance, for values that went through floating point rounding
+///
+/// Compound types like [`Vector`] are equal when every component is.
+pub trait ApproxEq {
+    type Epsilon: Copy;
+
+    /// Tolerance used by [`assert_approx_eq!`](crate::assert_approx_eq) when none is given
+    const DEFAULT_EPSILON: Self::Epsilon;
+
+    /// `|a - b| <= epsilon`; best for values near zero
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool;
+
+    /// `|a - b| <= max_relative * max(|a|, |b|)`; best for values far from zero
+    fn relative_eq(&self, other: &Self, max_relative: Self::Epsilon) -> bool;
+
+    /// At most `max_ulps` representable values apart; `0.0` and `-0.0` are equal
+    fn ulps_eq(&self, other: &Self, max_ulps: u32) -> bool;
+
+    /// [`abs_diff_eq`](Self::abs_diff_eq) with [`DEFAULT_EPSILON`](Self::DEFAULT_EPSILON)
+    #[inline]
+    fn approx_eq(&self, other: &Self) -> bool { self.abs_diff_eq(other, Self::DEFAULT_EPSILON) }
+}
+
+macro_rules! impl_approx_eq_float {
+    ($($T:ty),+) => {
+        $(impl ApproxEq for $T {
+            type Epsilon = $T;
+
+            const DEFAULT_EPSILON: Self::Epsilon = <$T>::EPSILON;
+
+            fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+                self == other || (self - other).abs() <= epsilon
+            }
+
+            fn relative_eq(&self, other: &Self, max_relative: Self::Epsilon) -> bool {
+                self == other || (self - other).abs() <= max_relative * self.abs().max(other.abs())
+            }
+
+            fn ulps_eq(&self, other: &Self, max_ulps: u32) -> bool {
+                if self.is_nan() || other.is_nan() { return false; }
+                if self.is_sign_negative() != other.is_sign_negative() { return self == other; }
+                self.to_bits().abs_diff(other.to_bits()) <= max_ulps.into()
+            }
+        })+
+    };
+}
+
+impl_approx_eq_float!(f32, f64);
+
+macro_rules! impl_approx_eq_fixed {
+    ($($Fixed:ident $Wide:ty),+) => {
+        $(impl<const FRAC_BITS: u32> ApproxEq for $Fixed<FRAC_BITS> {
+            type Epsilon = Self;
+
+            const DEFAULT_EPSILON: Self::Epsilon = Self::from_bits(1);
+
+            fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+                self.to_bits().abs_diff(other.to_bits()) <= epsilon.to_bits().unsigned_abs()
+            }
+
+            fn relative_eq(&self, other: &Self, max_relative: Self::Epsilon) -> bool {
+                // widened, so neither `MIN` nor the product can overflow
+                let largest = self.to_bits().unsigned_abs().max(other.to_bits().unsigned_abs()) as $Wide;
+                let tolerance = largest * max_relative.to_bits().unsigned_abs() as $Wide >> FRAC_BITS;
+                self.to_bits().abs_diff(other.to_bits()) as $Wide <= tolerance
+            }
+
+            fn ulps_eq(&self, other: &Self, max_ulps: u32) -> bool {
+                self.to_bits().abs_diff(other.to_bits()) <= max_ulps.into()
+            }
+        })+
+    };
+}
+
+impl_approx_eq_fixed!(Fixed u64, Fixed64 u128);
+
+impl<T: ApproxEq, const N: usize> ApproxEq for Vector<T, N> {
+    type Epsilon = T::Epsilon;
+
+    const DEFAULT_EPSILON: Self::Epsilon = T::DEFAULT_EPSILON;
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.0.iter().zip(&other.0).all(#[inline] |(a, b)| a.abs_diff_eq(b, epsilon))
+    }
+
+    fn relative_eq(&self, other: &Self, max_relative: Self::Epsilon) -> bool {
+        self.0.iter().zip(&other.0).all(#[inline] |(a, b)| a.relative_eq(b, max_relative))
+    }
+
+    fn ulps_eq(&self, other: &Self, max_ulps: u32) -> bool {
+        self.0.iter().zip(&other.0).all(#[inline] |(a, b)| a.ulps_eq(b, max_ulps))
+    }
+}
+
+impl<T: ApproxEq, const R: usize, const C: usize> ApproxEq for Matrix<T, R, C> {
+    type Epsilon = T::Epsilon;
+
+    const DEFAULT_EPSILON: Self::Epsilon = T::DEFAULT_EPSILON;
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.0.iter().zip(&other.0).all(#[inline] |(a, b)| a.abs_diff_eq(b, epsilon))
+    }
+
+    fn relative_eq(&self, other: &Self, max_relative: Self::Epsilon) -> bool {
+        self.0.iter().zip(&other.0).all(#[inline] |(a, b)| a.relative_eq(b, max_relative))
+    }
+
+    fn ulps_eq(&self, other: &Self, max_ulps: u32) -> bool {
+        self.0.iter().zip(&other.0).all(#[inline] |(a, b)| a.ulps_eq(b, max_ulps))
+    }
+}
+
+/// Compares components, so `q` and `-q` are not equal even though they are the same rotation
+impl<T: ApproxEq> ApproxEq for Quat<T> {
+    type Epsilon = T::Epsilon;
+
+    const DEFAULT_EPSILON: Self::Epsilon = T::DEFAULT_EPSILON;
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool { self.0.abs_diff_eq(&other.0, epsilon) }
+    fn relative_eq(&self, other: &Self, max_relative: Self::Epsilon) -> bool { self.0.relative_eq(&other.0, max_relative) }
+    fn ulps_eq(&self, other: &Self, max_ulps: u32) -> bool { self.0.ulps_eq(&other.0, max_ulps) }
+}
+
+impl<T: ApproxEq, const N: usize> ApproxEq for Affine<T, N> {
+    type Epsilon = T::Epsilon;
+
+    const DEFAULT_EPSILON: Self::Epsilon = T::DEFAULT_EPSILON;
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.linear.abs_diff_eq(&other.linear, epsilon) && self.translation.abs_diff_eq(&other.translation, epsilon)
+    }
+
+    fn relative_eq(&self, other: &Self, max_relative: Self::Epsilon) -> bool {
+        self.linear.relative_eq(&other.linear, max_relative) && self.translation.relative_eq(&other.translation, max_relative)
+    }
+
+    fn ulps_eq(&self, other: &Self, max_ulps: u32) -> bool {
+        self.linear.ulps_eq(&other.linear, max_ulps) && self.translation.ulps_eq(&other.translation, max_ulps)
+    }
+}
+
+/// Implementation detail of the assertion macros
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __approx_eq {
+    ($a:expr, $b:expr) => { $crate::approx_eq::ApproxEq::approx_eq($a, $b) };
+    ($a:expr, $b:expr, epsilon = $e:expr) => { $crate::approx_eq::ApproxEq::abs_diff_eq($a, $b, $e) };
+    ($a:expr, $b:expr, relative = $e:expr) => { $crate::approx_eq::ApproxEq::relative_eq($a, $b, $e) };
+    ($a:expr, $b:expr, ulps = $n:expr) => { $crate::approx_eq::ApproxEq::ulps_eq($a, $b, $n) };
+}
+
+/// Assert that two [`ApproxEq`] values are approximately equal
+///
+/// ```
+/// # use amy_math::{prelude::*, assert_approx_eq};
+/// assert_approx_eq!(0.1 + 0.2, 0.3_f64);
+/// assert_approx_eq!(Vec2::new(1.0, 2.0), Vec2::new(1.0001, 2.0), epsilon = 1e-3);
+/// assert_approx_eq!(1e9_f32, 1.00001e9, relative = 1e-4);
+/// assert_approx_eq!(1.0_f32, 1.0 + f32::EPSILON, ulps = 1);
+/// ```
+#[macro_export]
+macro_rules! assert_approx_eq {
+    ($a:expr, $b:expr $(, $mode:ident = $e:expr)? $(,)?) => {
+        match (&$a, &$b) {
+            (a, b) => if !$crate::__approx_eq!(a, b $(, $mode = $e)?) {
+                panic!(
+                    "assertion `left ≈ right` failed{}\n  left: {:?}\n right: {:?}",
+                    concat!($(" (", stringify!($mode), " = ", stringify!($e), ")")?), a, b,
+                );
+            }
+        }
+    };
+}
+
+/// Assert that two [`ApproxEq`] values are not approximately equal
+///
+/// ```
+/// # use amy_math::{prelude::*, assert_approx_ne};
+/// assert_approx_ne!(Vec2::new(1.0, 2.0), Vec2::new(1.1, 2.0), epsilon = 1e-3);
+/// ```
+#[macro_export]
+macro_rules! assert_approx_ne {
+    ($a:expr, $b:expr $(, $mode:ident = $e:expr)? $(,)?) => {
+        match (&$a, &$b) {
+            (a, b) => if $crate::__approx_eq!(a, b $(, $mode = $e)?) {
+                panic!(
+                    "assertion `left ≉ right` failed{}\n  left: {:?}\n right: {:?}",
+                    concat!($(" (", stringify!($mode), " = ", stringify!($e), ")")?), a, b,
+                );
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_float() {
+        assert!(1.0_f32.abs_diff_eq(&1.05, 0.1));
+        assert!(!1.0_f32.abs_diff_eq(&1.2, 0.1));
+        assert!(1e10_f64.relative_eq(&1.000001e10, 1e-5));
+        assert!(!1e-10_f64.relative_eq(&2e-10, 1e-5));
+        assert!(0.0_f32.ulps_eq(&-0.0, 0));
+        assert!(1.0_f32.ulps_eq(&f32::from_bits(1.0_f32.to_bits() + 2), 2));
+        assert!(!1.0_f32.ulps_eq(&f32::from_bits(1.0_f32.to_bits() + 3), 2));
+        assert!(!f64::NAN.ulps_eq(&f64::NAN, u32::MAX));
+    }
+
+    #[test]
+    fn test_fixed() {
+        type F = Fixed<16>;
+        assert!(F::from_f64(1000.0).relative_eq(&F::from_f64(1000.5), F::from_f64(1e-3)));
+        assert!(!F::from_f64(1000.0).relative_eq(&F::from_f64(1002.0), F::from_f64(1e-3)));
+        // neither `MIN.abs()` nor `MIN * 2.0` can be represented
+        assert!(F::from_bits(i32::MIN).relative_eq(&F::from_bits(i32::MIN + 1), F::from_f64(2.0)));
+        assert!(!F::from_bits(i32::MIN).relative_eq(&F::from_bits(i32::MAX), F::from_f64(0.5)));
+        assert!(Fixed64::<32>::from_bits(i64::MIN).relative_eq(&Fixed64::from_bits(i64::MAX), Fixed64::from_f64(2.0)));
+    }
+
+    #[test]
+    fn test_compound() {
+        assert_approx_eq!(Vec3::new(0.1, 0.2, 0.3) * 3.0, Vec3::new(0.3, 0.6, 0.9), ulps = 2);
+        assert_approx_ne!(Vec3::new(0.1, 0.2, 0.3), Vec3::new(0.1, 0.2, 0.31), epsilon = 1e-3);
+        assert_approx_eq!(Mat2::identity() * 0.1 * 10.0, Mat2::identity());
+        assert_approx_eq!(Quat::<f64>::identity(), Quat::new(0.0, 0.0, 1e-12, 1.0), epsilon = 1e-9);
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion `left ≈ right` failed (epsilon = 0.01)")]
+    fn test_assert_message() {
+        assert_approx_eq!(1.0_f32, 1.1, epsilon = 0.01);
+    }
+}
@@ -0,0 +1,200 @@
+//! [`Vector`] math over many vectors at once, stored as columns (structure of arrays)
+//!
+//! Each kernel takes one slice per component, as returned by the `cols`/`cols_mut` methods of a
+//! [`MultiVec`](crate::containers::multi_vec). Component-wise kernels run down each column;
+//! kernels that combine components work on [`LANES`] rows at a time using `Vector<T, LANES>`.
+//! Both compile to packed instructions. Results match the scalar per-vector methods of
+//! [`Vector`] exactly; the `simd` feature only changes the latter.
+//!
+//! ```
+//! # use amy_math::{batch, containers::multi_vec::multi_vec3::MultiVec3};
+//! let mut dirs = MultiVec3::<f32, f32, f32>::from(([3.0, 0.0], [4.0, 0.0], [0.0, 2.0]));
+//! let (x, y, z) = dirs.cols_mut();
+//! batch::normalize([x, y, z]);
+//! assert_eq!(dirs.row(0), (&0.6, &0.8, &0.0));
+//! assert_eq!(dirs.row(1), (&0.0, &0.0, &1.0));
+//! ```
+
+use crate::{math::*, num::*, vec::*};
+
+/// Rows processed together
+pub const LANES: usize = 8;
+
+/// The rows `start..start + LANES` of each column; rows past the end are filled with ones
+#[inline]
+fn gather<T: Num, const N: usize>(cols: [&[T]; N], start: usize) -> [Vector<T, LANES>; N] {
+    std::array::from_fn(#[inline] |c| {
+        let col = &cols[c][start..];
+        match col.get(..LANES) {
+            Some(chunk) => Vector(chunk.try_into().unwrap()),
+            None => {
+                let mut lanes = Vector::one();
+                lanes.0[..col.len()].copy_from_slice(col);
+                lanes
+            }
+        }
+    })
+}
+
+#[inline]
+fn scatter<T: Num>(col: &mut [T], start: usize, lanes: Vector<T, LANES>) {
+    let col = &mut col[start..];
+    let n = col.len().min(LANES);
+    col[..n].copy_from_slice(&lanes.0[..n]);
+}
+
+#[inline]
+fn rows<T, const N: usize>(cols: &[&[T]; N]) -> usize {
+    let len = cols.first().map_or(0, #[inline] |col| col.len());
+    assert!(cols.iter().all(#[inline] |col| col.len() == len), "columns must have the same length");
+    len
+}
+
+#[inline]
+fn reborrow<'a, T, const N: usize>(cols: &'a [&mut [T]; N]) -> [&'a [T]; N] {
+    std::array::from_fn(#[inline] |c| &*cols[c])
+}
+
+/// Replace each vector with `f` of itself, [`LANES`] rows at a time.
+/// Full groups of rows skip the padding, so that loop vectorizes.
+#[inline]
+fn map_in_place<T: Num, const N: usize>(mut cols: [&mut [T]; N], f: impl Fn([Vector<T, LANES>; N]) -> [Vector<T, LANES>; N]) {
+    let rows = rows(&reborrow(&cols));
+    let full_rows = rows - rows % LANES;
+    for start in (0..full_rows).step_by(LANES) {
+        let out = f(std::array::from_fn(#[inline] |c| Vector(cols[c][start..start + LANES].try_into().unwrap())));
+        for (col, lanes) in cols.iter_mut().zip(out) { col[start..start + LANES].copy_from_slice(&lanes.0); }
+    }
+    if full_rows < rows {
+        let out = f(gather(reborrow(&cols), full_rows));
+        for (col, lanes) in cols.iter_mut().zip(out) { scatter(col, full_rows, lanes); }
+    }
+}
+
+/// Write `f` of each vector to `out`, [`LANES`] rows at a time
+#[inline]
+fn reduce_into<T: Num, const N: usize>(cols: [&[T]; N], out: &mut [T], f: impl Fn([Vector<T, LANES>; N]) -> Vector<T, LANES>) {
+    let rows = rows(&cols);
+    assert_eq!(rows, out.len(), "output must have a row for each vector");
+    let full_rows = rows - rows % LANES;
+    for start in (0..full_rows).step_by(LANES) {
+        let lanes = f(std::array::from_fn(#[inline] |c| Vector(cols[c][start..start + LANES].try_into().unwrap())));
+        out[start..start + LANES].copy_from_slice(&lanes.0);
+    }
+    if full_rows < rows {
+        scatter(out, full_rows, f(gather(cols, full_rows)));
+    }
+}
+
+/// Sum of `a[c] * b[c]` over the components, per lane, in the same order as [`DotProduct`]
+#[inline]
+fn dot_lanes<T: Num, const N: usize>(a: &[Vector<T, LANES>; N], b: &[Vector<T, LANES>; N]) -> Vector<T, LANES> {
+    a.iter().zip(b).fold(Vector::zero(), #[inline] |acc, (a, b)| acc + *a * *b)
+}
+
+/// `dst += src`
+pub fn add<T: Num, const N: usize>(dst: [&mut [T]; N], src: [&[T]; N]) {
+    assert_eq!(rows(&reborrow(&dst)), rows(&src), "columns must have the same length");
+    for (dst, src) in dst.into_iter().zip(src) {
+        dst.iter_mut().zip(src).for_each(#[inline] |(d, s)| *d += *s);
+    }
+}
+
+/// `dst *= s`
+pub fn scale<T: Num, const N: usize>(dst: [&mut [T]; N], s: T) {
+    let _ = rows(&reborrow(&dst));
+    for col in dst { col.iter_mut().for_each(#[inline] |comp| *comp *= s); }
+}
+
+/// `out[i] = a[i].dot(b[i])`, accumulated one component at a time in the same order as [`DotProduct`]
+pub fn dot<T: Num, const N: usize>(a: [&[T]; N], b: [&[T]; N], out: &mut [T]) {
+    assert_eq!(rows(&a), rows(&b), "columns must have the same length");
+    assert_eq!(rows(&a), out.len(), "output must have a row for each vector");
+    out.fill(T::zero());
+    for (a, b) in a.into_iter().zip(b) {
+        out.iter_mut().zip(a.iter().zip(b)).for_each(#[inline] |(o, (a, b))| *o += *a * *b);
+    }
+}
+
+/// `out[i] = v[i].len()`
+pub fn len<T: Real, const N: usize>(v: [&[T]; N], out: &mut [T]) {
+    reduce_into(v, out, #[inline] |v| dot_lanes(&v, &v).map(T::sqrt));
+}
+
+/// Replace each vector with [`Normalize::norm`] of itself
+pub fn normalize<T: Real, const N: usize>(v: [&mut [T]; N]) {
+    map_in_place(v, #[inline] |v| {
+        let recip = dot_lanes(&v, &v).map(#[inline] |len_sqr| len_sqr.sqrt().recip());
+        v.map(#[inline] |comp| comp * recip)
+    });
+}
+
+/// Replace each vector with [`QNormalize::qnorm`] of itself, using [`q_rsqrt`] per lane
+pub fn qnormalize<T: Num + QRsqrt, const N: usize>(v: [&mut [T]; N]) {
+    map_in_place(v, #[inline] |v| {
+        let scale = dot_lanes(&v, &v).map(T::q_rsqrt);
+        v.map(#[inline] |comp| comp * scale)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::containers::multi_vec::multi_vec3::MultiVec3;
+
+    fn sample(rows: usize, phase: f32) -> (Vec<Vec3>, MultiVec3<f32, f32, f32>) {
+        let aos: Vec<Vec3> = (0..rows).map(|i| {
+            let t = i as f32 * 0.37 + phase;
+            Vec3::new(t.sin() * 5.0, t.cos() - 0.3, (t * 1.7).fract() + 0.01)
+        }).collect();
+        let soa = aos.iter().map(|v| (v[0], v[1], v[2])).collect();
+        (aos, soa)
+    }
+
+    fn assert_matches(aos: &[Vec3], soa: &MultiVec3<f32, f32, f32>) {
+        for (i, v) in aos.iter().enumerate() {
+            assert_eq!(soa.row(i), (&v[0], &v[1], &v[2]), "row {i}");
+        }
+    }
+
+    /// The scalar definitions, independent of the `simd` feature
+    fn dot3(a: Vec3, b: Vec3) -> f32 { a[0] * b[0] + a[1] * b[1] + a[2] * b[2] }
+
+    #[test]
+    fn test_kernels() {
+        // not a multiple of LANES, so the padded tail is exercised
+        let (aos, mut soa) = sample(21, 0.1);
+        let (other_aos, other) = sample(21, 2.0);
+
+        let mut out = vec![0.0; 21];
+        dot(<[&[f32]; 3]>::from(soa.cols()), other.cols().into(), &mut out);
+        assert_eq!(out, aos.iter().zip(&other_aos).map(|(a, b)| dot3(*a, *b)).collect::<Vec<_>>());
+        len(soa.cols().into(), &mut out);
+        assert_eq!(out, aos.iter().map(|v| dot3(*v, *v).sqrt()).collect::<Vec<_>>());
+
+        let (x, y, z) = soa.cols_mut();
+        add([x, y, z], other.cols().into());
+        let mut aos: Vec<Vec3> = aos.iter().zip(&other_aos).map(|(a, b)| a + b).collect();
+        assert_matches(&aos, &soa);
+
+        let (x, y, z) = soa.cols_mut();
+        scale([x, y, z], 0.5);
+        aos.iter_mut().for_each(|v| *v *= 0.5);
+        assert_matches(&aos, &soa);
+
+        let (x, y, z) = soa.cols_mut();
+        normalize([x, y, z]);
+        let aos: Vec<Vec3> = aos.iter().map(|v| *v * dot3(*v, *v).sqrt().recip()).collect();
+        assert_matches(&aos, &soa);
+
+        let (x, y, z) = soa.cols_mut();
+        qnormalize([x, y, z]);
+        assert_matches(&aos.iter().map(|v| *v * q_rsqrt(dot3(*v, *v))).collect::<Vec<_>>(), &soa);
+    }
+
+    #[test]
+    #[should_panic(expected = "columns must have the same length")]
+    fn test_mismatched_columns() {
+        scale([&mut [1.0, 2.0][..], &mut [1.0][..]], 2.0);
+    }
+}
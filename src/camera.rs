@@ -0,0 +1,292 @@
+//! View and projection matrices, and mapping between world and screen space
+//!
+//! Matrices transform column vectors, so a point goes through `proj * view * p`. Normalized device
+//! coordinates (NDC) have x and y in `[-1, 1]`, y up, and depth in `[0, 1]` as in Vulkan, Direct3D,
+//! Metal and WebGPU. Right-handed views look down -Z, left-handed views down +Z.
+//!
+//! Reverse-Z maps the near plane to depth 1 and the far plane to 0, which spreads floating point
+//! precision much more evenly over the depth range; use it with a "greater" depth test.
+
+use crate::{num::*, vec::*, mat::*, angle::*, interp::small};
+
+impl<T: Real> Matrix<T, 4, 4> {
+    /// `(x scale, y scale)` of a perspective projection
+    #[inline]
+    fn focal(fov_y: impl Into<Radians<T>>, aspect: T) -> (T, T) {
+        let f = (fov_y.into() / small(2)).tan().recip();
+        (f / aspect, f)
+    }
+
+    /// Perspective projection for a right-handed view, with vertical field of view `fov_y`
+    /// and `aspect = width / height`
+    pub fn perspective_rh(fov_y: impl Into<Radians<T>>, aspect: T, near: T, far: T) -> Self {
+        let (x, y) = Self::focal(fov_y, aspect);
+        let (zero, range) = (T::zero(), (near - far).recip());
+        Self::new(
+            x,    zero, zero,        zero,
+            zero, y,    zero,        zero,
+            zero, zero, far * range, near * far * range,
+            zero, zero, -T::one(),   zero,
+        )
+    }
+    /// Perspective projection for a left-handed view; see [`Self::perspective_rh`]
+    pub fn perspective_lh(fov_y: impl Into<Radians<T>>, aspect: T, near: T, far: T) -> Self {
+        let (x, y) = Self::focal(fov_y, aspect);
+        let (zero, range) = (T::zero(), (far - near).recip());
+        Self::new(
+            x,    zero, zero,        zero,
+            zero, y,    zero,        zero,
+            zero, zero, far * range, -near * far * range,
+            zero, zero, T::one(),    zero,
+        )
+    }
+    /// [`Self::perspective_rh`] with the far plane at infinity
+    pub fn perspective_infinite_rh(fov_y: impl Into<Radians<T>>, aspect: T, near: T) -> Self {
+        let (x, y) = Self::focal(fov_y, aspect);
+        let zero = T::zero();
+        Self::new(
+            x,    zero, zero,      zero,
+            zero, y,    zero,      zero,
+            zero, zero, -T::one(), -near,
+            zero, zero, -T::one(), zero,
+        )
+    }
+    /// [`Self::perspective_lh`] with the far plane at infinity
+    pub fn perspective_infinite_lh(fov_y: impl Into<Radians<T>>, aspect: T, near: T) -> Self {
+        let (x, y) = Self::focal(fov_y, aspect);
+        let zero = T::zero();
+        Self::new(
+            x,    zero, zero,     zero,
+            zero, y,    zero,     zero,
+            zero, zero, T::one(), -near,
+            zero, zero, T::one(), zero,
+        )
+    }
+    /// [`Self::perspective_rh`] with reverse-Z: `near` maps to depth 1 and `far` to 0
+    pub fn perspective_reverse_rh(fov_y: impl Into<Radians<T>>, aspect: T, near: T, far: T) -> Self { Self::perspective_rh(fov_y, aspect, far, near) }
+    /// [`Self::perspective_lh`] with reverse-Z: `near` maps to depth 1 and `far` to 0
+    pub fn perspective_reverse_lh(fov_y: impl Into<Radians<T>>, aspect: T, near: T, far: T) -> Self { Self::perspective_lh(fov_y, aspect, far, near) }
+    /// [`Self::perspective_infinite_rh`] with reverse-Z: `near` maps to depth 1 and infinity to 0
+    pub fn perspective_infinite_reverse_rh(fov_y: impl Into<Radians<T>>, aspect: T, near: T) -> Self {
+        let (x, y) = Self::focal(fov_y, aspect);
+        let zero = T::zero();
+        Self::new(
+            x,    zero, zero,      zero,
+            zero, y,    zero,      zero,
+            zero, zero, zero,      near,
+            zero, zero, -T::one(), zero,
+        )
+    }
+    /// [`Self::perspective_infinite_lh`] with reverse-Z: `near` maps to depth 1 and infinity to 0
+    pub fn perspective_infinite_reverse_lh(fov_y: impl Into<Radians<T>>, aspect: T, near: T) -> Self {
+        let (x, y) = Self::focal(fov_y, aspect);
+        let zero = T::zero();
+        Self::new(
+            x,    zero, zero,     zero,
+            zero, y,    zero,     zero,
+            zero, zero, zero,     near,
+            zero, zero, T::one(), zero,
+        )
+    }
+
+    /// Orthographic projection of the box `left..right`, `bottom..top`, `near..far` in front of a
+    /// right-handed view. Swap `near` and `far` for reverse-Z.
+    pub fn orthographic_rh(left: T, right: T, bottom: T, top: T, near: T, far: T) -> Self {
+        let depth = (near - far).recip();
+        Self::orthographic(left, right, bottom, top, depth, near * depth)
+    }
+    /// Orthographic projection in front of a left-handed view; see [`Self::orthographic_rh`]
+    pub fn orthographic_lh(left: T, right: T, bottom: T, top: T, near: T, far: T) -> Self {
+        let depth = (far - near).recip();
+        Self::orthographic(left, right, bottom, top, depth, -near * depth)
+    }
+    /// Depth is `z * z_scale + z_offset`
+    #[inline]
+    fn orthographic(left: T, right: T, bottom: T, top: T, z_scale: T, z_offset: T) -> Self {
+        let (width, height) = ((right - left).recip(), (top - bottom).recip());
+        let zero = T::zero();
+        Self::new(
+            width * small(2), zero,              zero,    -(right + left) * width,
+            zero,             height * small(2), zero,    -(top + bottom) * height,
+            zero,             zero,              z_scale, z_offset,
+            zero,             zero,              zero,    T::one(),
+        )
+    }
+
+    /// View matrix of a camera at `eye` looking at `target`, for a right-handed projection
+    pub fn look_at_rh(eye: Vector<T, 3>, target: Vector<T, 3>, up: Vector<T, 3>) -> Self {
+        Self::look_to(eye, -eye.dir(target), up)
+    }
+    /// View matrix of a camera at `eye` looking at `target`, for a left-handed projection
+    pub fn look_at_lh(eye: Vector<T, 3>, target: Vector<T, 3>, up: Vector<T, 3>) -> Self {
+        Self::look_to(eye, eye.dir(target), up)
+    }
+    /// View matrix mapping `eye` to the origin, `z_axis` to +Z and `up` towards +Y
+    #[inline]
+    fn look_to(eye: Vector<T, 3>, z_axis: Vector<T, 3>, up: Vector<T, 3>) -> Self {
+        let x_axis = up.cross(z_axis).norm();
+        let y_axis = z_axis.cross(x_axis);
+        let zero = T::zero();
+        let row = |axis: Vector<T, 3>| { let Vector([x, y, z]) = axis; [x, y, z, -axis.dot(eye)] };
+        let ([a, b, c, x], [d, e, f, y], [g, h, i, z]) = (row(x_axis), row(y_axis), row(z_axis));
+        Self::new(
+            a,    b,    c,    x,
+            d,    e,    f,    y,
+            g,    h,    i,    z,
+            zero, zero, zero, T::one(),
+        )
+    }
+}
+
+/// A rectangle of the screen in pixels, with y pointing down from the top left corner
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewport<T> {
+    pub x: T,
+    pub y: T,
+    pub width: T,
+    pub height: T,
+}
+
+impl<T: Real> Viewport<T> {
+    pub fn new(x: T, y: T, width: T, height: T) -> Self { Self { x, y, width, height } }
+
+    /// Screen position and depth of a world space point, [`None`] if it is in the camera's plane
+    /// or behind it
+    #[must_use]
+    pub fn project(&self, view_proj: &Matrix<T, 4, 4>, point: Vector<T, 3>) -> Option<Vector<T, 3>> {
+        let Vector([x, y, z]) = point;
+        let Vector([x, y, z, w]) = *view_proj * Vector([x, y, z, T::one()]);
+        if w <= T::zero() { return None; }
+        let (x, y, z) = (x / w, y / w, z / w);
+        Some(Vector([
+            self.x + (x + T::one()) / small(2) * self.width,
+            self.y + (T::one() - y) / small(2) * self.height,
+            z,
+        ]))
+    }
+
+    /// World space point at a screen position and depth, [`None`] if `view_proj` is singular
+    #[must_use]
+    pub fn unproject(&self, view_proj: &Matrix<T, 4, 4>, screen: Vector<T, 3>) -> Option<Vector<T, 3>> {
+        let Vector([sx, sy, depth]) = screen;
+        let x = (sx - self.x) / self.width * small(2) - T::one();
+        let y = T::one() - (sy - self.y) / self.height * small(2);
+        let Vector([x, y, z, w]) = view_proj.inverse()? * Vector([x, y, depth, T::one()]);
+        if w.is_zero() { return None; }
+        Some(Vector([x / w, y / w, z / w]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+
+    type DMat4 = Matrix<f64, 4, 4>;
+
+    fn ndc(m: &DMat4, p: DVec3) -> DVec3 {
+        let Vector([x, y, z, w]) = *m * Vector([p[0], p[1], p[2], 1.0]);
+        DVec3::new(x / w, y / w, z / w)
+    }
+
+    /// Corners of the frustum with a 90° vertical field of view, aspect 2, near 1 and far 10,
+    /// with `z` the signed distance along the view direction, and their NDC `(x, y)`
+    fn frustum_corners(z_sign: f64) -> impl Iterator<Item = (DVec3, f64, f64, bool)> {
+        [(1.0, true), (10.0, false)].into_iter().flat_map(move |(dist, near)| {
+            [(-1.0, -1.0), (1.0, -1.0), (-1.0, 1.0), (1.0, 1.0)].map(|(x, y)| (DVec3::new(x * 2.0 * dist, y * dist, dist * z_sign), x, y, near))
+        })
+    }
+
+    #[test]
+    fn test_perspective_corners() {
+        let (fov, aspect) = (Degrees(90.0), 2.0);
+        for (m, z_sign, reverse) in [
+            (DMat4::perspective_rh(fov, aspect, 1.0, 10.0), -1.0, false),
+            (DMat4::perspective_lh(fov, aspect, 1.0, 10.0), 1.0, false),
+            (DMat4::perspective_reverse_rh(fov, aspect, 1.0, 10.0), -1.0, true),
+            (DMat4::perspective_reverse_lh(fov, aspect, 1.0, 10.0), 1.0, true),
+        ] {
+            for (p, x, y, near) in frustum_corners(z_sign) {
+                let depth = if near != reverse { 0.0 } else { 1.0 };
+                assert_approx_eq!(ndc(&m, p), DVec3::new(x, y, depth), epsilon = 1e-12);
+            }
+        }
+
+        for (m, z_sign, reverse) in [
+            (DMat4::perspective_infinite_rh(fov, aspect, 1.0), -1.0, false),
+            (DMat4::perspective_infinite_lh(fov, aspect, 1.0), 1.0, false),
+            (DMat4::perspective_infinite_reverse_rh(fov, aspect, 1.0), -1.0, true),
+            (DMat4::perspective_infinite_reverse_lh(fov, aspect, 1.0), 1.0, true),
+        ] {
+            let (near_depth, far_depth) = if reverse { (1.0, 0.0) } else { (0.0, 1.0) };
+            for (p, x, y, near) in frustum_corners(z_sign).filter(|corner| corner.3) {
+                assert_approx_eq!(ndc(&m, p), DVec3::new(x, y, near_depth), epsilon = 1e-12);
+                // far away, the depth approaches the far plane's
+                let far = ndc(&m, p * 1e9);
+                assert_approx_eq!(far, DVec3::new(x, y, far_depth), epsilon = 1e-8);
+                assert!(if reverse { far[2] > 0.0 } else { far[2] < 1.0 }, "{near}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_orthographic_corners() {
+        for (m, z_sign) in [
+            (DMat4::orthographic_rh(-4.0, 2.0, -1.0, 3.0, 0.5, 8.0), -1.0),
+            (DMat4::orthographic_lh(-4.0, 2.0, -1.0, 3.0, 0.5, 8.0), 1.0),
+        ] {
+            for (x, ndc_x) in [(-4.0, -1.0), (2.0, 1.0)] {
+                for (y, ndc_y) in [(-1.0, -1.0), (3.0, 1.0)] {
+                    for (z, depth) in [(0.5, 0.0), (8.0, 1.0)] {
+                        assert_approx_eq!(ndc(&m, DVec3::new(x, y, z * z_sign)), DVec3::new(ndc_x, ndc_y, depth), epsilon = 1e-12);
+                    }
+                }
+            }
+        }
+        // reverse-Z by swapping near and far
+        let m = DMat4::orthographic_rh(-1.0, 1.0, -1.0, 1.0, 8.0, 0.5);
+        assert_approx_eq!(ndc(&m, DVec3::new(0.0, 0.0, -0.5)), DVec3::new(0.0, 0.0, 1.0), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_look_at() {
+        let (eye, target, up) = (DVec3::new(1.0, 2.0, 3.0), DVec3::new(1.0, 2.0, -2.0), DVec3::new(0.0, 1.0, 0.0));
+        let view = DMat4::look_at_rh(eye, target, up);
+        assert_approx_eq!(ndc(&view, eye), DVec3::zero(), epsilon = 1e-12);
+        assert_approx_eq!(ndc(&view, target), DVec3::new(0.0, 0.0, -5.0), epsilon = 1e-12);
+        assert_approx_eq!(ndc(&view, eye + up), DVec3::new(0.0, 1.0, 0.0), epsilon = 1e-12);
+        assert_approx_eq!(ndc(&view, DVec3::new(2.0, 2.0, 3.0)), DVec3::new(1.0, 0.0, 0.0), epsilon = 1e-12);
+
+        let view = DMat4::look_at_lh(eye, target, up);
+        assert_approx_eq!(ndc(&view, target), DVec3::new(0.0, 0.0, 5.0), epsilon = 1e-12);
+        assert_approx_eq!(ndc(&view, DVec3::new(0.0, 2.0, 3.0)), DVec3::new(1.0, 0.0, 0.0), epsilon = 1e-12);
+
+        // an oblique view keeps the basis orthonormal
+        let view = DMat4::look_at_rh(DVec3::new(-3.0, 4.0, 1.0), DVec3::new(2.0, -1.0, 0.5), up);
+        let linear = Matrix::<f64, 3, 3>::from_rows(std::array::from_fn(|i| { let Vector([x, y, z, _]) = *view.row(i); DVec3::new(x, y, z) }));
+        assert_approx_eq!(linear * linear.transpose(), Matrix::identity(), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_project_unproject() {
+        let viewport = Viewport::new(100.0, 50.0, 800.0, 600.0);
+        let view_proj = DMat4::perspective_rh(Degrees(90.0), 800.0 / 600.0, 0.1, 100.0)
+            * DMat4::look_at_rh(DVec3::new(0.0, 0.0, 5.0), DVec3::zero(), DVec3::new(0.0, 1.0, 0.0));
+
+        // NDC corners land on the viewport corners
+        let inv = view_proj.inverse().unwrap();
+        for ((x, y), screen) in [((-1.0, 1.0), (100.0, 50.0)), ((1.0, 1.0), (900.0, 50.0)), ((-1.0, -1.0), (100.0, 650.0)), ((1.0, -1.0), (900.0, 650.0))] {
+            let world = ndc(&inv, DVec3::new(x, y, 0.5));
+            assert_approx_eq!(viewport.project(&view_proj, world).unwrap(), DVec3::new(screen.0, screen.1, 0.5), epsilon = 1e-9);
+        }
+        assert_approx_eq!(viewport.project(&view_proj, DVec3::zero()).unwrap().xy(), DVec2::new(500.0, 350.0), epsilon = 1e-9);
+        // behind the camera
+        assert_eq!(viewport.project(&view_proj, DVec3::new(0.0, 0.0, 6.0)), None);
+
+        for p in [DVec3::new(1.0, -2.0, 0.0), DVec3::new(-3.0, 0.5, -20.0), DVec3::new(0.1, 0.2, 4.8)] {
+            let screen = viewport.project(&view_proj, p).unwrap();
+            assert_approx_eq!(viewport.unproject(&view_proj, screen).unwrap(), p, epsilon = 1e-9);
+        }
+        assert_eq!(viewport.unproject(&DMat4::from([[0.0; 4]; 4]), DVec3::zero()), None);
+    }
+}
@@ -3,19 +3,19 @@ pub mod multi_vec;
 /// Helpers for testing/debugging custom collections
 #[cfg(test)]
 pub mod tracking {
-    use std::{cell::Cell, sync::Arc};
+    use std::{cell::Cell, rc::Rc};
 
     #[derive(Debug)]
     pub struct Tracked {
-        clones: Arc<Cell<usize>>,
-        drops:  Arc<Cell<usize>>,
+        clones: Rc<Cell<usize>>,
+        drops:  Rc<Cell<usize>>,
     }
 
     impl Tracked {
         pub fn new() -> Self {
             Self {
-                clones: Arc::new(Cell::new(0)),
-                drops:  Arc::new(Cell::new(0)),
+                clones: Rc::new(Cell::new(0)),
+                drops:  Rc::new(Cell::new(0)),
             }
         }
 
@@ -23,6 +23,12 @@ pub mod tracking {
         pub fn times_dropped(&self) -> usize { self.drops .get() }
     }
 
+    impl Default for Tracked {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
     impl Clone for Tracked {
         fn clone(&self) -> Self {
             self.clones.set(self.clones.get() + 1);
@@ -92,6 +92,11 @@ macro_rules! impl_multivec {
                         self.len
                     }
 
+                    /// Check whether the vec has no rows
+                    pub fn is_empty(&self) -> bool {
+                        self.len == 0
+                    }
+
                     /// Append a row to the end of the vec
                     pub fn push(&mut self, $([<col $n>]: [<T $n>]),+) {
                         if self.len == self.buf.cap { self.buf.grow(); }
@@ -114,6 +119,7 @@ macro_rules! impl_multivec {
                     }
 
                     /// Insert an item at an index
+                    #[allow(clippy::too_many_arguments)]
                     pub fn insert(&mut self, index: usize, $([<col $n>]: [<T $n>]),+) {
                         // Note: `<=` because it's valid to insert after everything
                         // which would be equivalent to push.
@@ -173,10 +179,16 @@ macro_rules! impl_multivec {
                     )+
 
                     /// Get all columns as a tuple of slices
-                    pub fn cols<'a>(&'a self) -> ($(&'a [[<T $n>]]),+) {
+                    pub fn cols(&self) -> ($(&[[<T $n>]]),+) {
                         ($(self.[<col $n>]()),+)
                     }
 
+                    /// Get all columns as a tuple of mutable slices
+                    pub fn cols_mut(&mut self) -> ($(&mut [[<T $n>]]),+) {
+                        // the columns are separate allocations, so the slices never alias
+                        ($(unsafe { std::slice::from_raw_parts_mut::<[<T $n>]>(self.buf.ptrs.$n.as_ptr(), self.len) }),+)
+                    }
+
                     /// Extend with an iterator over tuples of rows
                     pub fn extend_rows<T: IntoIterator<Item = ($([<T $n>]),+)>>(&mut self, iter: T) {
                         for row in iter {
@@ -200,6 +212,12 @@ macro_rules! impl_multivec {
                     }
                 }
 
+                impl<$([<T $n>]),+> Default for [<MultiVec $count>]<$([<T $n>]),+> {
+                    fn default() -> Self {
+                        Self::new()
+                    }
+                }
+
                 impl<$([<T $n>]),+> Drop for [<MultiVec $count>]<$([<T $n>]),+> {
                     fn drop(&mut self) {
                         while let Some(_) = self.pop() {}
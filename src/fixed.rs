@@ -0,0 +1,333 @@
+//! Deterministic fixed-point numbers
+//!
+//! Every operation, including roots and trigonometry, uses integer arithmetic only, so results are
+//! bit-identical across platforms, compilers and optimization levels.
+
+use std::{fmt, ops::*};
+use crate::num::*;
+
+/// Transcendental functions work at this many fractional bits, in an `i128`
+const Q: u32 = 60;
+const ONE_Q: i128 = 1 << Q;
+const PI_Q: i128 = 3622009729038561421;
+const LN2_Q: i128 = 799144290325165979;
+/// `∏ 1 / sqrt(1 + 2^-2i)`, the inverse of the CORDIC rotation gain
+const CORDIC_GAIN_Q: i128 = 700114967507363238;
+/// `atan(2^-i)`
+const ATAN_Q: [i128; 61] = [
+    0x0c90fdaa22168c23, 0x076b19c1586ed3da, 0x03eb6ebf25901bac, 0x01fd5ba9aac2f6dc,
+    0x00ffaaddb967ef4e, 0x007ff556eea5d893, 0x003ffeaab776e535, 0x001fffd555bbba97,
+    0x000ffffaaaaddddc, 0x0007ffff55556eef, 0x0003ffffeaaaab77, 0x0001fffffd55555c,
+    0x0000ffffffaaaaab, 0x00007ffffff55555, 0x00003ffffffeaaab, 0x00001fffffffd555,
+    0x00000ffffffffaab, 0x000007ffffffff55, 0x000003ffffffffeb, 0x000001fffffffffd,
+    0x0000010000000000, 0x0000008000000000, 0x0000004000000000, 0x0000002000000000,
+    0x0000001000000000, 0x0000000800000000, 0x0000000400000000, 0x0000000200000000,
+    0x0000000100000000, 0x0000000080000000, 0x0000000040000000, 0x0000000020000000,
+    0x0000000010000000, 0x0000000008000000, 0x0000000004000000, 0x0000000002000000,
+    0x0000000001000000, 0x0000000000800000, 0x0000000000400000, 0x0000000000200000,
+    0x0000000000100000, 0x0000000000080000, 0x0000000000040000, 0x0000000000020000,
+    0x0000000000010000, 0x0000000000008000, 0x0000000000004000, 0x0000000000002000,
+    0x0000000000001000, 0x0000000000000800, 0x0000000000000400, 0x0000000000000200,
+    0x0000000000000100, 0x0000000000000080, 0x0000000000000040, 0x0000000000000020,
+    0x0000000000000010, 0x0000000000000008, 0x0000000000000004, 0x0000000000000002,
+    0x0000000000000001,
+];
+
+/// `(sin z, cos z)` by CORDIC rotation
+fn sin_cos_q(z: i128) -> (i128, i128) {
+    // reduce to [-π/2, π/2], where CORDIC converges
+    let mut z = z.rem_euclid(2 * PI_Q);
+    if z > PI_Q { z -= 2 * PI_Q; }
+    let flip = !(-PI_Q / 2..=PI_Q / 2).contains(&z);
+    if z > PI_Q / 2 { z = PI_Q - z; } else if z < -PI_Q / 2 { z = -PI_Q - z; }
+
+    let (mut x, mut y) = (CORDIC_GAIN_Q, 0);
+    for (i, &a) in ATAN_Q.iter().enumerate() {
+        let (dx, dy) = (y >> i, x >> i);
+        if z >= 0 { x -= dx; y += dy; z -= a; } else { x += dx; y -= dy; z += a; }
+    }
+    (y, if flip { -x } else { x })
+}
+
+/// `atan2(y, x)` by CORDIC vectoring; `y` and `x` may be at any common scale
+fn atan2_q(y: i128, x: i128) -> i128 {
+    if x == 0 && y == 0 { return 0; }
+    // use as many bits as possible while leaving headroom for the CORDIC gain
+    let shift = (x.unsigned_abs() | y.unsigned_abs()).leading_zeros().saturating_sub(5);
+    let (mut x, mut y) = (x << shift, y << shift);
+    let mut z = 0;
+    if x < 0 {
+        (x, y, z) = (-x, -y, if y >= 0 { PI_Q } else { -PI_Q });
+    }
+    for (i, &a) in ATAN_Q.iter().enumerate() {
+        let (dx, dy) = (y >> i, x >> i);
+        if y > 0 { x += dx; y -= dy; z += a; } else { x -= dx; y += dy; z -= a; }
+    }
+    z
+}
+
+/// `e^x`, saturating to `i128::MAX`
+fn exp_q(x: i128) -> i128 {
+    // e^x = 2^k * e^r with |r| <= ln(2) / 2
+    let k = (x + LN2_Q / 2).div_euclid(LN2_Q);
+    let r = x - k * LN2_Q;
+    let (mut sum, mut term, mut n) = (ONE_Q, ONE_Q, 1);
+    while term != 0 {
+        term = ((term * r) >> Q) / n;
+        sum += term;
+        n += 1;
+    }
+    match k {
+        ..-64 => 0,
+        -64..0 => (sum + (1 << (-k - 1))) >> -k,
+        0..=65 => sum << k,
+        _ => i128::MAX,
+    }
+}
+
+/// `ln(x)` for `x > 0`
+fn ln_q(x: i128) -> i128 {
+    // x = 2^k * m with m in [1, 2), and ln(m) = 2 atanh((m - 1) / (m + 1))
+    let k = (127 - x.leading_zeros() as i128) - Q as i128;
+    let m = if k >= 0 { x >> k } else { x << -k };
+    let s = ((m - ONE_Q) << Q) / (m + ONE_Q);
+    let s2 = (s * s) >> Q;
+    let (mut sum, mut power, mut n) = (0, s, 1);
+    while power != 0 {
+        sum += power / n;
+        power = (power * s2) >> Q;
+        n += 2;
+    }
+    k * LN2_Q + 2 * sum
+}
+
+macro_rules! impl_fixed {
+    ($($Fixed:ident $T:ident $Wide:ident $UWide:ident $max_frac_bits:literal $doc:literal),+) => {
+        $(
+        #[doc = $doc]
+        ///
+        /// `FRAC_BITS` of the underlying integer hold the fraction. Addition, subtraction and
+        /// remainder are exact; multiplication rounds to nearest and division truncates.
+        /// Overflow panics in debug builds, like the primitive integers.
+        ///
+        /// Division by a scalar goes through [`Recip`], so `v / s` multiplies by `1 / s` and loses
+        /// some precision when `|s|` is large.
+        #[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $Fixed<const FRAC_BITS: u32>($T);
+
+        impl<const FRAC_BITS: u32> $Fixed<FRAC_BITS> {
+            pub const ZERO: Self = Self(0);
+            pub const ONE: Self = Self(1 << FRAC_BITS);
+            pub const MIN: Self = Self($T::MIN);
+            pub const MAX: Self = Self($T::MAX);
+
+            /// The number whose underlying integer is `bits`
+            pub const fn from_bits(bits: $T) -> Self { Self(bits) }
+            /// The underlying integer
+            pub const fn to_bits(self) -> $T { self.0 }
+            pub const fn from_int(n: $T) -> Self { Self(n << FRAC_BITS) }
+            /// Rounds towards negative infinity
+            pub const fn to_int(self) -> $T { self.0 >> FRAC_BITS }
+            /// `π * 2^(k - 1)`, rounded to nearest
+            const fn pi_scaled(k: u32) -> Self {
+                const { assert!(FRAC_BITS <= $max_frac_bits, concat!("`FRAC_BITS` must be at most ", $max_frac_bits, " to fit the constants in `Real`")) };
+                Self((((PI_Q >> (Q - FRAC_BITS - k)) + 1) >> 1) as $T)
+            }
+            /// Rounds to the nearest representable value and saturates; for setup, not simulation
+            pub fn from_f64(x: f64) -> Self { Self((x * (1u128 << FRAC_BITS) as f64).round() as $T) }
+            pub fn to_f64(self) -> f64 { self.0 as f64 / (1u128 << FRAC_BITS) as f64 }
+
+            fn to_q(self) -> i128 {
+                if FRAC_BITS <= Q { (self.0 as i128) << (Q - FRAC_BITS) } else { self.0 as i128 >> (FRAC_BITS - Q) }
+            }
+
+            /// Rounds to nearest and saturates
+            fn from_q(q: i128) -> Self {
+                let bits = if FRAC_BITS <= Q {
+                    let shift = Q - FRAC_BITS;
+                    if shift == 0 { q } else { (q >> (shift - 1)).saturating_add(1) >> 1 }
+                } else {
+                    q.saturating_mul(1 << (FRAC_BITS - Q))
+                };
+                Self(bits.clamp($T::MIN as i128, $T::MAX as i128) as $T)
+            }
+        }
+
+        impl<const FRAC_BITS: u32> fmt::Debug for $Fixed<FRAC_BITS> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { fmt::Debug::fmt(&self.to_f64(), f) }
+        }
+        impl<const FRAC_BITS: u32> fmt::Display for $Fixed<FRAC_BITS> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { fmt::Display::fmt(&self.to_f64(), f) }
+        }
+
+        impl<const FRAC_BITS: u32> Neg for $Fixed<FRAC_BITS> { type Output = Self; fn neg(self) -> Self::Output { Self(-self.0) } }
+        impl<const FRAC_BITS: u32> Add for $Fixed<FRAC_BITS> { type Output = Self; fn add(self, rhs: Self) -> Self::Output { Self(self.0 + rhs.0) } }
+        impl<const FRAC_BITS: u32> Sub for $Fixed<FRAC_BITS> { type Output = Self; fn sub(self, rhs: Self) -> Self::Output { Self(self.0 - rhs.0) } }
+        impl<const FRAC_BITS: u32> Rem for $Fixed<FRAC_BITS> { type Output = Self; fn rem(self, rhs: Self) -> Self::Output { Self(self.0 % rhs.0) } }
+        impl<const FRAC_BITS: u32> Mul for $Fixed<FRAC_BITS> {
+            type Output = Self;
+            fn mul(self, rhs: Self) -> Self::Output {
+                let wide = self.0 as $Wide * rhs.0 as $Wide;
+                let wide = if FRAC_BITS == 0 { wide } else { ((wide >> (FRAC_BITS - 1)) + 1) >> 1 };
+                debug_assert!(wide as $T as $Wide == wide, "attempt to multiply with overflow");
+                Self(wide as $T)
+            }
+        }
+        impl<const FRAC_BITS: u32> Div for $Fixed<FRAC_BITS> {
+            type Output = Self;
+            fn div(self, rhs: Self) -> Self::Output {
+                let wide = ((self.0 as $Wide) << FRAC_BITS) / rhs.0 as $Wide;
+                debug_assert!(wide as $T as $Wide == wide, "attempt to divide with overflow");
+                Self(wide as $T)
+            }
+        }
+
+        impl<const FRAC_BITS: u32> AddAssign for $Fixed<FRAC_BITS> { fn add_assign(&mut self, rhs: Self) { *self = *self + rhs; } }
+        impl<const FRAC_BITS: u32> SubAssign for $Fixed<FRAC_BITS> { fn sub_assign(&mut self, rhs: Self) { *self = *self - rhs; } }
+        impl<const FRAC_BITS: u32> MulAssign for $Fixed<FRAC_BITS> { fn mul_assign(&mut self, rhs: Self) { *self = *self * rhs; } }
+        impl<const FRAC_BITS: u32> DivAssign for $Fixed<FRAC_BITS> { fn div_assign(&mut self, rhs: Self) { *self = *self / rhs; } }
+        impl<const FRAC_BITS: u32> RemAssign for $Fixed<FRAC_BITS> { fn rem_assign(&mut self, rhs: Self) { *self = *self % rhs; } }
+
+        impl<const FRAC_BITS: u32> Zero for $Fixed<FRAC_BITS> { fn zero() -> Self { Self::ZERO } fn is_zero(&self) -> bool { self.0 == 0 } }
+        impl<const FRAC_BITS: u32> One for $Fixed<FRAC_BITS> { fn one() -> Self { Self::ONE } }
+        impl<const FRAC_BITS: u32> Recip for $Fixed<FRAC_BITS> { fn recip(self) -> Self { Self::ONE / self } }
+        /// Negative inputs give zero
+        impl<const FRAC_BITS: u32> Sqrt for $Fixed<FRAC_BITS> {
+            fn sqrt(self) -> Self {
+                if self.0 <= 0 { return Self::ZERO; }
+                Self((((self.0 as $UWide) << FRAC_BITS).isqrt()) as $T)
+            }
+        }
+        impl<const FRAC_BITS: u32> Num for $Fixed<FRAC_BITS> {}
+
+        impl<const FRAC_BITS: u32> Signed for $Fixed<FRAC_BITS> {
+            fn abs(self) -> Self { Self(self.0.abs()) }
+            fn signum(self) -> Self { Self::from_int(self.0.signum()) }
+            fn is_negative(self) -> bool { self.0 < 0 }
+        }
+
+        /// Out-of-domain inputs give the nearest sensible value instead of NaN:
+        /// `asin`/`acos` clamp to `[-1, 1]`, `ln` of a non-positive number is `MIN`,
+        /// `powf` of a non-positive base is zero, and overflowing results saturate.
+        impl<const FRAC_BITS: u32> Real for $Fixed<FRAC_BITS> {
+            const PI: Self = Self::pi_scaled(1);
+            const TAU: Self = Self::pi_scaled(2);
+            const FRAC_PI_2: Self = Self::pi_scaled(0);
+            const EPSILON: Self = Self(1);
+
+            fn floor(self) -> Self { Self(self.0 & !(Self::ONE.0 - 1)) }
+            fn ceil(self) -> Self { -(-self).floor() }
+            fn round(self) -> Self { if self.0 < 0 { -(-self).round() } else { Self((self.0 + (Self::ONE.0 >> 1)) & !(Self::ONE.0 - 1)) } }
+            fn trunc(self) -> Self { if self.0 < 0 { self.ceil() } else { self.floor() } }
+            fn fract(self) -> Self { self - self.trunc() }
+            fn mul_add(self, a: Self, b: Self) -> Self { self * a + b }
+            fn sin(self) -> Self { Self::from_q(sin_cos_q(self.to_q()).0) }
+            fn cos(self) -> Self { Self::from_q(sin_cos_q(self.to_q()).1) }
+            fn tan(self) -> Self {
+                let (sin, cos) = sin_cos_q(self.to_q());
+                Self::from_q(if cos == 0 { sin.signum() * i128::MAX } else { (sin << Q) / cos })
+            }
+            fn sin_cos(self) -> (Self, Self) { let (sin, cos) = sin_cos_q(self.to_q()); (Self::from_q(sin), Self::from_q(cos)) }
+            fn asin(self) -> Self {
+                let x = self.to_q().clamp(-ONE_Q, ONE_Q);
+                Self::from_q(atan2_q(x, ((ONE_Q * ONE_Q - x * x) as u128).isqrt() as i128))
+            }
+            fn acos(self) -> Self {
+                let x = self.to_q().clamp(-ONE_Q, ONE_Q);
+                Self::from_q(atan2_q(((ONE_Q * ONE_Q - x * x) as u128).isqrt() as i128, x))
+            }
+            fn atan(self) -> Self { Self::from_q(atan2_q(self.0 as i128, Self::ONE.0 as i128)) }
+            fn atan2(self, other: Self) -> Self { Self::from_q(atan2_q(self.0 as i128, other.0 as i128)) }
+            fn exp(self) -> Self { Self::from_q(exp_q(self.to_q())) }
+            fn ln(self) -> Self {
+                let x = self.to_q();
+                if x <= 0 { Self::MIN } else { Self::from_q(ln_q(x)) }
+            }
+            fn powf(self, n: Self) -> Self {
+                let x = self.to_q();
+                if x <= 0 { return Self::ZERO; }
+                let ln = ln_q(x);
+                Self::from_q(match ln.checked_mul(n.to_q()) {
+                    Some(p) => exp_q(p >> Q),
+                    None if (ln < 0) != n.is_negative() => 0,
+                    None => i128::MAX,
+                })
+            }
+        }
+        )+
+    };
+}
+
+impl_fixed! {
+    Fixed i32 i64 u64 28 "A fixed-point number in an `i32`; `FRAC_BITS` can be at most 28 to fit the constants in [`Real`]",
+    Fixed64 i64 i128 u128 58 "A fixed-point number in an `i64`; `FRAC_BITS` can be at most 58 to fit the constants in [`Real`]"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{vec::*, assert_approx_eq};
+
+    type F16 = Fixed<16>;
+    type F32 = Fixed64<32>;
+
+    #[test]
+    fn test_arith() {
+        let (a, b) = (F16::from_f64(2.5), F16::from_f64(-0.75));
+        assert_eq!(a + b, F16::from_f64(1.75));
+        assert_eq!(a * b, F16::from_f64(-1.875));
+        assert_eq!(a / b, F16::from_bits(-218453));
+        assert_eq!(a % F16::ONE, F16::from_f64(0.5));
+        assert_eq!(F16::from_int(7).to_int(), 7);
+        assert_eq!(b.floor(), -F16::ONE);
+        assert_eq!(b.ceil(), F16::ZERO);
+        assert_eq!(b.round(), -F16::ONE);
+        assert_eq!(b.trunc(), F16::ZERO);
+        assert_eq!(F16::from_int(2).sqrt(), F16::from_bits(92681));
+        assert_eq!(F16::from_int(-4).sqrt(), F16::ZERO);
+        assert_eq!(format!("{:.3}", a), "2.500");
+    }
+
+    #[test]
+    fn test_transcendental() {
+        let tol = 1e-8;
+        let eps = F32::from_f64(tol);
+        for i in -200..=200 {
+            let x = i as f64 * 0.05;
+            let f = F32::from_f64(x);
+            let x = f.to_f64();
+            assert_approx_eq!(f.sin(), F32::from_f64(x.sin()), epsilon = eps);
+            assert_approx_eq!(f.cos(), F32::from_f64(x.cos()), epsilon = eps);
+            assert_approx_eq!(f.atan(), F32::from_f64(x.atan()), epsilon = eps);
+            assert_approx_eq!(f.atan2(F32::from_f64(-1.5)), F32::from_f64(x.atan2(-1.5)), epsilon = eps);
+            assert_approx_eq!(f.exp(), F32::from_f64(x.exp()), epsilon = F32::from_f64(tol.max(x.exp() * tol)));
+            if x > 0.0 {
+                assert_approx_eq!(f.ln(), F32::from_f64(x.ln()), epsilon = eps);
+                assert_approx_eq!(f.powf(F32::from_f64(1.5)), F32::from_f64(x.powf(1.5)), epsilon = F32::from_f64(tol.max(x.powf(1.5) * tol)));
+            }
+            if x.abs() <= 1.0 {
+                assert_approx_eq!(f.asin(), F32::from_f64(x.asin()), epsilon = eps);
+                assert_approx_eq!(f.acos(), F32::from_f64(x.acos()), epsilon = eps);
+            }
+        }
+        assert_approx_eq!(F32::PI, F32::from_f64(std::f64::consts::PI), epsilon = eps);
+        assert_eq!(F16::PI, F16::from_f64(std::f64::consts::PI));
+        // the largest `FRAC_BITS` the constants fit in
+        assert_eq!(Fixed::<28>::TAU, Fixed::<28>::from_f64(std::f64::consts::TAU));
+        assert_approx_eq!(Fixed64::<58>::TAU, Fixed64::<58>::from_f64(std::f64::consts::TAU), ulps = 512);
+        assert_eq!(F16::FRAC_PI_2.sin(), F16::ONE);
+        assert_eq!(F16::ONE.atan2(F16::ONE), F16::from_f64(std::f64::consts::FRAC_PI_4));
+        assert_eq!(F16::from_int(100).exp(), F16::MAX);
+        assert_eq!(F16::ZERO.ln(), F16::MIN);
+    }
+
+    #[test]
+    fn test_vector() {
+        let v = Vector::<F16, 3>::new(F16::from_int(3), F16::from_int(0), F16::from_int(-4));
+        assert_eq!(v.len(), F16::from_int(5));
+        assert_eq!(v.dist(Vector::zero()), F16::from_int(5));
+        let n = v.norm();
+        assert!((n.x().to_f64() - 0.6).abs() < 1e-4 && (n.z().to_f64() + 0.8).abs() < 1e-4, "{n:?}");
+        assert!((n.len().to_f64() - 1.0).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,437 @@
+//! Intersection and overlap tests between the primitives of [`geom`](super)
+//!
+//! Ray casts return a [`Hit`], overlaps between solids return a [`Contact`]. Touching shapes count
+//! as intersecting. A ray starting inside a solid hits it at `t = 0`, facing back along the ray.
+
+use crate::{num::*, vec::*, interp::small};
+use super::*;
+
+/// Where a ray or segment first meets a shape
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hit<T, const N: usize> {
+    /// Parameter of the hit, in multiples of the ray direction or along the segment
+    pub t: T,
+    pub point: Vector<T, N>,
+    /// Unit surface normal, facing against the ray
+    pub normal: Vector<T, N>,
+}
+
+/// How two solids overlap
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Contact<T, const N: usize> {
+    /// A point in the overlap
+    pub point: Vector<T, N>,
+    /// Unit direction from the first shape into the second
+    pub normal: Vector<T, N>,
+    /// How far the second shape has to move along `normal` to only touch the first
+    pub depth: T,
+}
+
+pub trait Intersect<Rhs> {
+    type Output;
+    #[must_use]
+    fn intersect(&self, other: &Rhs) -> Option<Self::Output>;
+    fn intersects(&self, other: &Rhs) -> bool { self.intersect(other).is_some() }
+}
+
+/// Unit vector along axis `i`, scaled by `sign`
+#[inline]
+fn axis<T: Num, const N: usize>(i: usize, sign: T) -> Vector<T, N> {
+    let mut v = Vector::zero();
+    v[i] = sign;
+    v
+}
+
+impl<T: Real, const N: usize> Ray<T, N> {
+    /// The hit for a ray starting inside a solid
+    #[inline]
+    fn inside_hit(&self) -> Hit<T, N> { Hit { t: T::zero(), point: self.origin, normal: -self.dir.norm() } }
+}
+
+/// Slab test
+impl<T: Real, const N: usize> Intersect<Aabb<T, N>> for Ray<T, N> {
+    type Output = Hit<T, N>;
+    fn intersect(&self, aabb: &Aabb<T, N>) -> Option<Self::Output> {
+        let (mut t_enter, mut t_exit, mut enter_axis) = (T::zero(), None, None);
+        for i in 0..N {
+            let (o, d) = (self.origin[i], self.dir[i]);
+            if d.is_zero() {
+                if o < aabb.min[i] || o > aabb.max[i] { return None; }
+                continue;
+            }
+            let recip = d.recip();
+            let (t0, t1) = ((aabb.min[i] - o) * recip, (aabb.max[i] - o) * recip);
+            let (near, far) = if t0 < t1 { (t0, t1) } else { (t1, t0) };
+            if near > t_enter { (t_enter, enter_axis) = (near, Some(i)); }
+            if t_exit.is_none_or(|t_exit| far < t_exit) { t_exit = Some(far); }
+            if t_exit.is_some_and(|t_exit| t_exit < t_enter) { return None; }
+        }
+        let Some(i) = enter_axis else { return Some(self.inside_hit()) };
+        let sign = if self.dir[i] > T::zero() { -T::one() } else { T::one() };
+        Some(Hit { t: t_enter, point: self.at(t_enter), normal: axis(i, sign) })
+    }
+}
+
+impl<T: Real, const N: usize> Intersect<Sphere<T, N>> for Ray<T, N> {
+    type Output = Hit<T, N>;
+    fn intersect(&self, sphere: &Sphere<T, N>) -> Option<Self::Output> {
+        // |m + d t|² = r², solved for the smaller t
+        let m = self.origin - sphere.center;
+        let (a, b, c) = (self.dir.len_sqr(), m.dot(self.dir), m.len_sqr() - sphere.radius * sphere.radius);
+        if c <= T::zero() { return Some(self.inside_hit()); }
+        if b > T::zero() { return None; }
+        let discr = b * b - a * c;
+        if discr < T::zero() { return None; }
+        let t = (-b - discr.sqrt()) / a;
+        let point = self.at(t);
+        Some(Hit { t, point, normal: (point - sphere.center) / sphere.radius })
+    }
+}
+
+/// Möller–Trumbore; both faces are hit, and the normal faces the ray
+impl<T: Real> Intersect<Triangle<T, 3>> for Ray<T, 3> {
+    type Output = Hit<T, 3>;
+    fn intersect(&self, tri: &Triangle<T, 3>) -> Option<Self::Output> {
+        let (e1, e2) = (tri.b - tri.a, tri.c - tri.a);
+        let p = self.dir.cross(e2);
+        let det = e1.dot(p);
+        if det.is_zero() { return None; }
+        let recip = det.recip();
+        let s = self.origin - tri.a;
+        let u = s.dot(p) * recip;
+        if u < T::zero() || u > T::one() { return None; }
+        let q = s.cross(e1);
+        let v = self.dir.dot(q) * recip;
+        if v < T::zero() || u + v > T::one() { return None; }
+        let t = e2.dot(q) * recip;
+        if t < T::zero() { return None; }
+        let normal = e1.cross(e2).norm();
+        Some(Hit { t, point: self.at(t), normal: if normal.dot(self.dir) > T::zero() { -normal } else { normal } })
+    }
+}
+
+/// Both sides are hit, and the normal faces the ray
+impl<T: Real> Intersect<Plane<T>> for Ray<T, 3> {
+    type Output = Hit<T, 3>;
+    fn intersect(&self, plane: &Plane<T>) -> Option<Self::Output> {
+        let denom = plane.normal.dot(self.dir);
+        if denom.is_zero() { return None; }
+        let t = -plane.signed_dist(self.origin) / denom;
+        if t < T::zero() { return None; }
+        Some(Hit { t, point: self.at(t), normal: if denom > T::zero() { -plane.normal } else { plane.normal } })
+    }
+}
+
+/// Separates along the axis needing the shortest push
+impl<T: Real, const N: usize> Intersect<Aabb<T, N>> for Aabb<T, N> {
+    type Output = Contact<T, N>;
+    fn intersect(&self, other: &Aabb<T, N>) -> Option<Self::Output> {
+        let overlap = Aabb { min: self.min.max(other.min), max: self.max.min(other.max) };
+        if overlap.size().iter().any(|&len| len < T::zero()) { return None; }
+        let (mut depth, mut normal) = (self.max[0] - other.min[0], axis(0, T::one()));
+        for i in 0..N {
+            for (push, sign) in [(self.max[i] - other.min[i], T::one()), (other.max[i] - self.min[i], -T::one())] {
+                if push < depth { (depth, normal) = (push, axis(i, sign)); }
+            }
+        }
+        Some(Contact { point: overlap.center(), normal, depth })
+    }
+}
+
+impl<T: Real, const N: usize> Intersect<Sphere<T, N>> for Sphere<T, N> {
+    type Output = Contact<T, N>;
+    fn intersect(&self, other: &Sphere<T, N>) -> Option<Self::Output> {
+        let radii = self.radius + other.radius;
+        let dist_sqr = self.center.dist_sqr(other.center);
+        if dist_sqr > radii * radii { return None; }
+        let dist = dist_sqr.sqrt();
+        // concentric spheres can separate in any direction
+        let normal = if dist.is_zero() { axis(0, T::one()) } else { (other.center - self.center) / dist };
+        let depth = radii - dist;
+        Some(Contact { point: self.center + normal * (self.radius - depth / small(2)), normal, depth })
+    }
+}
+
+impl<T: Real, const N: usize> Intersect<Aabb<T, N>> for Sphere<T, N> {
+    type Output = Contact<T, N>;
+    fn intersect(&self, aabb: &Aabb<T, N>) -> Option<Self::Output> {
+        let closest = aabb.closest_point(self.center);
+        let dist_sqr = self.center.dist_sqr(closest);
+        if dist_sqr > self.radius * self.radius { return None; }
+        if !dist_sqr.is_zero() {
+            let dist = dist_sqr.sqrt();
+            return Some(Contact { point: closest, normal: (closest - self.center) / dist, depth: self.radius - dist });
+        }
+        // the center is inside the box: leave through the nearest face
+        let (mut face_dist, mut face) = (self.center[0] - aabb.min[0], axis(0, -T::one()));
+        for i in 0..N {
+            for (dist, sign) in [(self.center[i] - aabb.min[i], -T::one()), (aabb.max[i] - self.center[i], T::one())] {
+                if dist < face_dist { (face_dist, face) = (dist, axis(i, sign)); }
+            }
+        }
+        Some(Contact { point: self.center + face * face_dist, normal: -face, depth: face_dist + self.radius })
+    }
+}
+
+impl<T: Real, const N: usize> Intersect<Sphere<T, N>> for Aabb<T, N> {
+    type Output = Contact<T, N>;
+    fn intersect(&self, sphere: &Sphere<T, N>) -> Option<Self::Output> {
+        sphere.intersect(self).map(|contact| Contact { normal: -contact.normal, ..contact })
+    }
+}
+
+/// Crossing point of two 2D segments; `t` is along `self`, and the normal is that of `other`,
+/// facing `self.a`. Parallel segments, including overlapping collinear ones, never intersect.
+impl<T: Real> Intersect<Segment<T, 2>> for Segment<T, 2> {
+    type Output = Hit<T, 2>;
+    fn intersect(&self, other: &Segment<T, 2>) -> Option<Self::Output> {
+        let (r, s) = (self.b - self.a, other.b - other.a);
+        let denom = r.cross(s);
+        if denom.is_zero() { return None; }
+        let qp = other.a - self.a;
+        let (t, u) = (qp.cross(s) / denom, qp.cross(r) / denom);
+        let unit = |x: T| x >= T::zero() && x <= T::one();
+        if !unit(t) || !unit(u) { return None; }
+        let normal = Vector([-s[1], s[0]]).norm();
+        Some(Hit { t, point: self.at(t), normal: if normal.dot(r) > T::zero() { -normal } else { normal } })
+    }
+}
+
+impl<T: Real, const N: usize> Segment<T, N> {
+    /// The parameters `(s, t)` of the closest points on `self` and `other`.
+    /// Ericson, "Real-Time Collision Detection", 5.1.9
+    #[must_use]
+    pub fn closest_ts(&self, other: &Self) -> (T, T) {
+        let saturate = |x: T| if x < T::zero() { T::zero() } else if x > T::one() { T::one() } else { x };
+        let (d1, d2, r) = (self.b - self.a, other.b - other.a, self.a - other.a);
+        let (a, e, f) = (d1.len_sqr(), d2.len_sqr(), d2.dot(r));
+        if a.is_zero() { return (T::zero(), if e.is_zero() { T::zero() } else { saturate(f / e) }); }
+        let c = d1.dot(r);
+        if e.is_zero() { return (saturate(-c / a), T::zero()); }
+        let b = d1.dot(d2);
+        let denom = a * e - b * b;
+        let s = if denom.is_zero() { T::zero() } else { saturate((b * f - c * e) / denom) };
+        let t = (b * s + f) / e;
+        if t < T::zero() { (saturate(-c / a), T::zero()) }
+        else if t > T::one() { (saturate((b - c) / a), T::one()) }
+        else { (s, t) }
+    }
+    /// The closest points on `self` and `other`
+    #[must_use]
+    pub fn closest_points(&self, other: &Self) -> (Vector<T, N>, Vector<T, N>) {
+        let (s, t) = self.closest_ts(other);
+        (self.at(s), other.at(t))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+
+    /// Deterministic points spread over `[-scale, scale]³`
+    fn points(count: usize, seed: f32, scale: f32) -> impl Iterator<Item = Vec3> {
+        (0..count).map(move |i| {
+            let t = i as f32 * 0.618 + seed;
+            Vec3::new((t * 1.1).sin(), (t * 2.3).cos(), (t * 3.7).sin()) * scale
+        })
+    }
+
+    fn rays() -> impl Iterator<Item = Ray<f32, 3>> {
+        points(200, 0.0, 4.0).zip(points(200, 7.0, 1.0)).map(|(origin, target)| Ray::through(origin, target))
+    }
+
+    /// The first `t` (in steps of `STEP`, up to 20) at which the ray is inside the shape
+    const STEP: f32 = 1e-3;
+    fn march(ray: &Ray<f32, 3>, inside: impl Fn(Vec3) -> bool) -> Option<f32> {
+        (0..20_000).map(|i| i as f32 * STEP).find(|&t| inside(ray.at(t)))
+    }
+
+    #[test]
+    fn test_ray_aabb() {
+        let aabb = Aabb::new(Vec3::new(-1.0, -0.5, -2.0), Vec3::new(1.5, 0.5, 0.0));
+        let mut hits = 0;
+        for ray in rays() {
+            let hit = ray.intersect(&aabb);
+            match (hit, march(&ray, |p| aabb.contains_point(p))) {
+                (Some(hit), marched) => {
+                    hits += 1;
+                    assert!(aabb.grow(1e-4).contains_point(hit.point), "{ray:?}");
+                    assert!(marched.is_none_or(|t| t >= hit.t - STEP), "{ray:?}");
+                    // the normal is an axis, facing against the ray, and the point is on that face
+                    if hit.t > 0.0 {
+                        assert_eq!(hit.normal.abs().sum(), 1.0);
+                        assert!(hit.normal.dot(ray.dir) < 0.0);
+                        assert!(!aabb.contains_point(hit.point + hit.normal * 1e-3), "{ray:?}");
+                    }
+                }
+                (None, marched) => assert_eq!(marched, None, "{ray:?}"),
+            }
+        }
+        assert!(hits > 20, "{hits}");
+
+        // parallel to a slab, inside and outside of it
+        let ray = Ray::new(Vec3::new(-3.0, 0.0, -1.0), Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(ray.intersect(&aabb), Some(Hit { t: 2.0, point: Vec3::new(-1.0, 0.0, -1.0), normal: Vec3::new(-1.0, 0.0, 0.0) }));
+        assert_eq!(Ray::new(Vec3::new(-3.0, 1.0, -1.0), ray.dir).intersect(&aabb), None);
+        // from inside
+        let ray = Ray::new(Vec3::new(0.0, 0.0, -1.0), Vec3::new(0.0, 0.0, 2.0));
+        assert_eq!(ray.intersect(&aabb), Some(Hit { t: 0.0, point: ray.origin, normal: Vec3::new(0.0, 0.0, -1.0) }));
+    }
+
+    #[test]
+    fn test_ray_sphere() {
+        let sphere = Sphere::new(Vec3::new(0.5, -0.3, 0.2), 1.2);
+        let mut hits = 0;
+        for ray in rays() {
+            match (ray.intersect(&sphere), march(&ray, |p| sphere.contains_point(p))) {
+                (Some(hit), marched) => {
+                    hits += 1;
+                    assert!((hit.point.dist(sphere.center) - sphere.radius).abs() < 1e-4 || hit.t == 0.0, "{ray:?}");
+                    assert!(marched.is_none_or(|t| t >= hit.t - STEP), "{ray:?}");
+                    assert!((hit.normal.len() - 1.0).abs() < 1e-5 && hit.normal.dot(ray.dir) <= 0.0);
+                }
+                (None, marched) => assert_eq!(marched, None, "{ray:?}"),
+            }
+        }
+        assert!(hits > 20, "{hits}");
+
+        let ray = Ray::new(Vec3::new(0.0, 0.0, -5.0), Vec3::new(0.0, 0.0, 2.0));
+        assert_eq!(ray.intersect(&Sphere::new(Vec3::zero(), 1.0)), Some(Hit { t: 2.0, point: Vec3::new(0.0, 0.0, -1.0), normal: Vec3::new(0.0, 0.0, -1.0) }));
+        assert_eq!(ray.intersect(&Sphere::new(Vec3::new(0.0, 0.0, -6.0), 0.5)), None);
+    }
+
+    #[test]
+    fn test_ray_triangle_plane() {
+        let tri = Triangle::new(Vec3::new(-1.0, -1.0, 0.5), Vec3::new(2.0, 0.0, -0.5), Vec3::new(0.0, 1.5, 0.0));
+        let plane = tri.plane();
+        let mut hits = 0;
+        for ray in rays() {
+            let (hit, plane_hit) = (ray.intersect(&tri), ray.intersect(&plane));
+            // where the ray crosses the plane, sampled
+            let sign = plane.signed_dist(ray.origin).signum();
+            let crossing = march(&ray, |p| plane.signed_dist(p).signum() != sign);
+            assert!(crossing.is_none() || plane_hit.is_some(), "{ray:?}");
+            assert!(plane_hit.is_none_or(|hit| hit.t > 19.9 || crossing.is_some()), "{ray:?}");
+            if let (Some(plane_hit), Some(t)) = (plane_hit, crossing) {
+                assert!((plane_hit.t - t).abs() <= STEP && plane.signed_dist(plane_hit.point).abs() < 1e-5, "{ray:?}");
+                assert!(plane_hit.normal.dot(ray.dir) < 0.0);
+            }
+            // the triangle is hit where the plane is, if that point is inside it
+            let on_triangle = plane_hit.map(|hit| tri.closest_point(hit.point).dist(hit.point));
+            match hit {
+                Some(hit) => {
+                    hits += 1;
+                    assert_approx_eq!(hit.point, plane_hit.unwrap().point, epsilon = 1e-4);
+                    assert_eq!(hit.normal, plane_hit.unwrap().normal);
+                    assert!(on_triangle.unwrap() < 1e-4, "{ray:?}");
+                }
+                None => assert!(on_triangle.is_none_or(|dist| dist > 0.0), "{ray:?}"),
+            }
+        }
+        assert!(hits > 10, "{hits}");
+
+        let ray = Ray::new(Vec3::new(0.0, 0.0, 3.0), Vec3::new(0.0, 0.0, -1.0));
+        let flat = Triangle::new(Vec3::new(-1.0, -1.0, 0.0), Vec3::new(1.0, -1.0, 0.0), Vec3::new(0.0, 1.0, 0.0));
+        assert_eq!(ray.intersect(&flat), Some(Hit { t: 3.0, point: Vec3::zero(), normal: Vec3::new(0.0, 0.0, 1.0) }));
+        // parallel, and pointing away
+        assert_eq!(Ray::new(ray.origin, Vec3::new(1.0, 0.0, 0.0)).intersect(&flat), None);
+        assert_eq!(Ray::new(ray.origin, -ray.dir).intersect(&flat.plane()), None);
+    }
+
+    #[test]
+    fn test_aabb_aabb() {
+        // corners on a half-unit grid, so sampling at quarter units is exact
+        let boxes: Vec<_> = points(40, 0.3, 3.0).zip(points(40, 5.0, 1.5))
+            .map(|(c, h)| Aabb::from_center_half_extents((c * 2.0).round() / 2.0, (h.abs() * 2.0).ceil() / 2.0))
+            .collect();
+        let grid = |aabb: Aabb<f32, 3>| {
+            let steps = (aabb.size() * 4.0).cast::<usize>();
+            (0..=steps[0]).flat_map(move |x| (0..=steps[1]).flat_map(move |y| (0..=steps[2]).map(move |z| aabb.min + Vec3::new(x as f32, y as f32, z as f32) / 4.0)))
+        };
+        for a in &boxes {
+            for b in &boxes {
+                let sampled = grid(*a).any(|p| b.contains_point(p));
+                let contact = a.intersect(b);
+                assert_eq!(contact.is_some(), sampled, "{a:?} {b:?}");
+                if let Some(contact) = contact {
+                    assert!(a.contains_point(contact.point) && b.contains_point(contact.point));
+                    // moving `b` out of `a` leaves them touching
+                    let moved = Aabb { min: b.min + contact.normal * contact.depth, max: b.max + contact.normal * contact.depth };
+                    assert_eq!(a.intersect(&moved).map(|c| c.depth), Some(0.0), "{a:?} {b:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sphere_overlaps() {
+        let near = |s: &Sphere<f32, 3>, p: Vec3| p.dist(s.center) <= s.radius + 1e-4;
+        let spheres: Vec<_> = points(30, 0.9, 2.5).zip(points(30, 3.0, 1.0)).map(|(c, r)| Sphere::new(c, r[0].abs() + 0.2)).collect();
+        let boxes: Vec<_> = points(30, 4.4, 2.5).zip(points(30, 8.0, 1.0)).map(|(c, h)| Aabb::from_center_half_extents(c, h.abs() + 0.1)).collect();
+        let samples: Vec<_> = points(20_000, 0.0, 4.0).collect();
+        for a in &spheres {
+            for b in &spheres {
+                let contact = a.intersect(b);
+                if samples.iter().any(|&p| a.contains_point(p) && b.contains_point(p)) { assert!(contact.is_some()); }
+                let Some(contact) = contact else { continue };
+                assert!(near(a, contact.point) && near(b, contact.point), "{a:?} {b:?}");
+                let moved = Sphere::new(b.center + contact.normal * contact.depth, b.radius);
+                assert!((a.center.dist(moved.center) - a.radius - b.radius).abs() < 1e-4, "{a:?} {b:?}");
+            }
+            for b in &boxes {
+                let contact = a.intersect(b);
+                if samples.iter().any(|&p| a.contains_point(p) && b.contains_point(p)) { assert!(contact.is_some()); }
+                assert_eq!(contact.map(|c| -c.normal), b.intersect(a).map(|c| c.normal));
+                let Some(contact) = contact else { continue };
+                assert!(near(a, contact.point) && b.grow(1e-4).contains_point(contact.point), "{a:?} {b:?}");
+                let moved = Aabb { min: b.min + contact.normal * contact.depth, max: b.max + contact.normal * contact.depth };
+                assert!((moved.dist_sqr(a.center).sqrt() - a.radius).abs() < 1e-4, "{a:?} {b:?}");
+            }
+        }
+
+        let (a, b) = (Sphere::new(Vec2::new(0.0, 0.0), 1.0), Sphere::new(Vec2::new(1.5, 0.0), 1.0));
+        assert_eq!(a.intersect(&b), Some(Contact { point: Vec2::new(0.75, 0.0), normal: Vec2::new(1.0, 0.0), depth: 0.5 }));
+        assert_eq!(a.intersect(&Sphere::new(Vec2::new(2.5, 0.0), 1.0)), None);
+        // center inside the box
+        let aabb = Aabb::new(Vec2::new(-1.0, -3.0), Vec2::new(5.0, 0.5));
+        assert_eq!(a.intersect(&aabb), Some(Contact { point: Vec2::new(0.0, 0.5), normal: Vec2::new(0.0, -1.0), depth: 1.5 }));
+    }
+
+    #[test]
+    fn test_segments() {
+        let segments: Vec<_> = points(40, 0.1, 2.0).zip(points(40, 6.0, 2.0)).map(|(a, b)| Segment::new(a, b)).collect();
+        for a in &segments {
+            for b in &segments[..10] {
+                let (p, q) = a.closest_points(b);
+                let sampled = (0..=100).flat_map(|i| (0..=100).map(move |j| (i as f32 / 100.0, j as f32 / 100.0)))
+                    .map(|(s, t)| a.at(s).dist(b.at(t)))
+                    .fold(f32::INFINITY, f32::min);
+                assert!(p.dist(q) <= sampled + 1e-4 && sampled - p.dist(q) < 0.1, "{a:?} {b:?}");
+            }
+        }
+        let (a, b) = (Segment::new(Vec3::zero(), Vec3::new(2.0, 0.0, 0.0)), Segment::new(Vec3::new(1.0, 1.0, 1.0), Vec3::new(1.0, 1.0, 3.0)));
+        assert_eq!(a.closest_points(&b), (Vec3::new(1.0, 0.0, 0.0), Vec3::new(1.0, 1.0, 1.0)));
+        assert_eq!(a.closest_ts(&Segment::new(b.a, b.a)), (0.5, 0.0));
+
+        // 2D crossings against sampled distances
+        let flat: Vec<_> = segments.iter().map(|s| Segment::new(s.a.xy(), s.b.xy())).collect();
+        for a in &flat {
+            for b in &flat {
+                let hit = a.intersect(b);
+                let dist = { let (p, q) = a.closest_points(b); p.dist(q) };
+                match hit {
+                    Some(hit) => {
+                        assert!(dist < 1e-4 && b.closest_point(hit.point).dist(hit.point) < 1e-4, "{a:?} {b:?}");
+                        assert_approx_eq!(a.at(hit.t), hit.point);
+                        assert!(hit.normal.dot(b.b - b.a).abs() < 1e-5 && hit.normal.dot(a.b - a.a) <= 0.0);
+                    }
+                    None => assert!(dist > 1e-4 || (a.b - a.a).cross(b.b - b.a).abs() < 1e-3, "{a:?} {b:?}"),
+                }
+            }
+        }
+        let cross = Segment::new(Vec2::new(0.0, -1.0), Vec2::new(0.0, 3.0));
+        assert_eq!(cross.intersect(&Segment::new(Vec2::new(-1.0, 0.0), Vec2::new(1.0, 0.0))), Some(Hit { t: 0.25, point: Vec2::zero(), normal: Vec2::new(0.0, -1.0) }));
+        assert_eq!(cross.intersect(&Segment::new(Vec2::new(1.0, 0.0), Vec2::new(2.0, 0.0))), None);
+    }
+}
@@ -0,0 +1,393 @@
+//! Geometric primitives built on [`Vector`]
+//!
+//! Shapes are solid: a point inside an [`Aabb`], [`Sphere`] or [`Triangle`] is its own closest point.
+//! Directions are not required to be normalized unless stated, and line parameters `t` are in
+//! multiples of the direction, so `ray.at(t)` is `origin + dir * t`.
+
+use crate::{num::*, vec::*, interp::small};
+
+pub mod intersect;
+
+/// `t` clamped to `[0, 1]`
+#[inline]
+fn saturate<T: Num>(t: T) -> T {
+    if t < T::zero() { T::zero() } else if t > T::one() { T::one() } else { t }
+}
+
+/// Axis-aligned bounding box, containing the points with `min <= p <= max` in every component
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb<T, const N: usize> {
+    pub min: Vector<T, N>,
+    pub max: Vector<T, N>,
+}
+
+impl<T: Num, const N: usize> Aabb<T, N> {
+    /// The box with opposite corners `a` and `b`, in any order
+    pub fn new(a: Vector<T, N>, b: Vector<T, N>) -> Self { Self { min: a.min(b), max: a.max(b) } }
+    pub fn from_center_half_extents(center: Vector<T, N>, half_extents: Vector<T, N>) -> Self {
+        Self::new(center - half_extents, center + half_extents)
+    }
+    /// The smallest box containing every point, `None` if there are none
+    pub fn from_points(points: impl IntoIterator<Item = Vector<T, N>>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Self { min: first, max: first }, Self::expand_to))
+    }
+
+    #[must_use]
+    pub fn center(&self) -> Vector<T, N> { (self.min + self.max) / small::<T>(2) }
+    #[must_use]
+    pub fn size(&self) -> Vector<T, N> { self.max - self.min }
+    #[must_use]
+    pub fn half_extents(&self) -> Vector<T, N> { self.size() / small::<T>(2) }
+    /// Length, area or volume, depending on `N`
+    #[must_use]
+    pub fn volume(&self) -> T { self.size().product() }
+
+    pub fn contains_point(&self, point: Vector<T, N>) -> bool { (point.cmpge(self.min) & point.cmple(self.max)).all() }
+    pub fn contains(&self, other: &Self) -> bool { (other.min.cmpge(self.min) & other.max.cmple(self.max)).all() }
+
+    /// The smallest box containing both
+    #[must_use]
+    pub fn union(self, other: Self) -> Self { Self { min: self.min.min(other.min), max: self.max.max(other.max) } }
+    /// The smallest box containing `self` and `point`
+    #[must_use]
+    pub fn expand_to(self, point: Vector<T, N>) -> Self { Self { min: self.min.min(point), max: self.max.max(point) } }
+    /// Move every face outwards by `margin`, or inwards if it is negative
+    #[must_use]
+    pub fn grow(self, margin: T) -> Self { Self { min: self.min - margin, max: self.max + margin } }
+
+    #[must_use]
+    pub fn closest_point(&self, point: Vector<T, N>) -> Vector<T, N> { point.clamp(self.min, self.max) }
+    /// Squared distance from `point` to the box, zero inside
+    #[must_use]
+    pub fn dist_sqr(&self, point: Vector<T, N>) -> T { point.dist_sqr(self.closest_point(point)) }
+}
+impl<T: Num> Aabb<T, 2> {
+    #[must_use]
+    pub fn perimeter(&self) -> T { self.size().sum() * small(2) }
+}
+impl<T: Num> Aabb<T, 3> {
+    #[must_use]
+    pub fn surface_area(&self) -> T {
+        let Vector([x, y, z]) = self.size();
+        (x * y + y * z + z * x) * small(2)
+    }
+}
+
+/// Half-line from `origin` along `dir`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ray<T, const N: usize> {
+    pub origin: Vector<T, N>,
+    pub dir: Vector<T, N>,
+}
+
+impl<T: Num, const N: usize> Ray<T, N> {
+    pub fn new(origin: Vector<T, N>, dir: Vector<T, N>) -> Self { Self { origin, dir } }
+    #[must_use]
+    pub fn at(&self, t: T) -> Vector<T, N> { self.origin + self.dir * t }
+}
+impl<T: Real, const N: usize> Ray<T, N> {
+    /// The ray from `origin` through `target`, with a unit direction
+    pub fn through(origin: Vector<T, N>, target: Vector<T, N>) -> Self { Self { origin, dir: origin.dir(target) } }
+
+    /// The `t >= 0` of the point on the ray closest to `point`
+    #[must_use]
+    pub fn closest_t(&self, point: Vector<T, N>) -> T {
+        let t = (point - self.origin).dot(self.dir) / self.dir.len_sqr();
+        if t > T::zero() { t } else { T::zero() }
+    }
+    #[must_use]
+    pub fn closest_point(&self, point: Vector<T, N>) -> Vector<T, N> { self.at(self.closest_t(point)) }
+}
+
+/// Line segment from `a` to `b`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Segment<T, const N: usize> {
+    pub a: Vector<T, N>,
+    pub b: Vector<T, N>,
+}
+
+impl<T: Num, const N: usize> Segment<T, N> {
+    pub fn new(a: Vector<T, N>, b: Vector<T, N>) -> Self { Self { a, b } }
+    /// `a` at `t = 0`, `b` at `t = 1`
+    #[must_use]
+    pub fn at(&self, t: T) -> Vector<T, N> { self.a + (self.b - self.a) * t }
+    #[must_use]
+    pub fn midpoint(&self) -> Vector<T, N> { (self.a + self.b) / small::<T>(2) }
+    #[must_use]
+    pub fn len_sqr(&self) -> T { self.a.dist_sqr(self.b) }
+    #[must_use]
+    pub fn aabb(&self) -> Aabb<T, N> { Aabb::new(self.a, self.b) }
+}
+impl<T: Real, const N: usize> Segment<T, N> {
+    #[must_use]
+    pub fn len(&self) -> T { self.a.dist(self.b) }
+
+    /// The `t` in `[0, 1]` of the point on the segment closest to `point`
+    #[must_use]
+    pub fn closest_t(&self, point: Vector<T, N>) -> T {
+        let ab = self.b - self.a;
+        let len_sqr = ab.len_sqr();
+        if len_sqr.is_zero() { return T::zero(); }
+        saturate((point - self.a).dot(ab) / len_sqr)
+    }
+    #[must_use]
+    pub fn closest_point(&self, point: Vector<T, N>) -> Vector<T, N> { self.at(self.closest_t(point)) }
+}
+
+/// Solid ball (a disk in 2D)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sphere<T, const N: usize> {
+    pub center: Vector<T, N>,
+    pub radius: T,
+}
+
+impl<T: Num, const N: usize> Sphere<T, N> {
+    pub fn new(center: Vector<T, N>, radius: T) -> Self { Self { center, radius } }
+
+    pub fn contains_point(&self, point: Vector<T, N>) -> bool { self.center.dist_sqr(point) <= self.radius * self.radius }
+    #[must_use]
+    pub fn aabb(&self) -> Aabb<T, N> { Aabb { min: self.center - self.radius, max: self.center + self.radius } }
+}
+impl<T: Real, const N: usize> Sphere<T, N> {
+    pub fn contains(&self, other: &Self) -> bool {
+        other.radius <= self.radius && self.center.dist(other.center) + other.radius <= self.radius
+    }
+
+    /// The smallest sphere containing both
+    #[must_use]
+    pub fn union(self, other: Self) -> Self {
+        if self.contains(&other) { return self; }
+        if other.contains(&self) { return other; }
+        let dist = self.center.dist(other.center);
+        let radius = (dist + self.radius + other.radius) / small(2);
+        Self { center: self.center + (other.center - self.center) * ((radius - self.radius) / dist), radius }
+    }
+    /// The smallest sphere containing `self` and `point`
+    #[must_use]
+    pub fn expand_to(self, point: Vector<T, N>) -> Self { self.union(Self::new(point, T::zero())) }
+
+    #[must_use]
+    pub fn closest_point(&self, point: Vector<T, N>) -> Vector<T, N> {
+        if self.contains_point(point) { point } else { self.center + self.center.dir(point) * self.radius }
+    }
+}
+impl<T: Real> Sphere<T, 2> {
+    #[must_use]
+    pub fn area(&self) -> T { T::PI * self.radius * self.radius }
+    #[must_use]
+    pub fn circumference(&self) -> T { T::TAU * self.radius }
+}
+impl<T: Real> Sphere<T, 3> {
+    #[must_use]
+    pub fn volume(&self) -> T { T::PI * self.radius * self.radius * self.radius * small(4) / small::<T>(3) }
+    #[must_use]
+    pub fn surface_area(&self) -> T { T::PI * self.radius * self.radius * small(4) }
+}
+
+/// The points `p` with `normal.dot(p) == d`; `normal` is expected to be normalized
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Plane<T> {
+    pub normal: Vector<T, 3>,
+    pub d: T,
+}
+
+impl<T: Num> Plane<T> {
+    pub fn new(normal: Vector<T, 3>, d: T) -> Self { Self { normal, d } }
+    pub fn from_point_normal(point: Vector<T, 3>, normal: Vector<T, 3>) -> Self { Self { normal, d: normal.dot(point) } }
+
+    /// Positive on the side `normal` points to
+    #[must_use]
+    pub fn signed_dist(&self, point: Vector<T, 3>) -> T { self.normal.dot(point) - self.d }
+    /// The projection of `point` onto the plane
+    #[must_use]
+    pub fn closest_point(&self, point: Vector<T, 3>) -> Vector<T, 3> { point - self.normal * self.signed_dist(point) }
+}
+impl<T: Real> Plane<T> {
+    /// The plane through three points, facing the side they appear counter-clockwise from
+    pub fn from_points(a: Vector<T, 3>, b: Vector<T, 3>, c: Vector<T, 3>) -> Self {
+        Self::from_point_normal(a, (b - a).cross(c - a).norm())
+    }
+}
+
+/// Solid triangle with corners `a`, `b`, `c`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Triangle<T, const N: usize> {
+    pub a: Vector<T, N>,
+    pub b: Vector<T, N>,
+    pub c: Vector<T, N>,
+}
+
+impl<T: Num, const N: usize> Triangle<T, N> {
+    pub fn new(a: Vector<T, N>, b: Vector<T, N>, c: Vector<T, N>) -> Self { Self { a, b, c } }
+    #[must_use]
+    pub fn centroid(&self) -> Vector<T, N> { (self.a + self.b + self.c) / small::<T>(3) }
+    #[must_use]
+    pub fn aabb(&self) -> Aabb<T, N> { Aabb::new(self.a, self.b).expand_to(self.c) }
+}
+impl<T: Real, const N: usize> Triangle<T, N> {
+    #[must_use]
+    pub fn area(&self) -> T {
+        // Lagrange's identity, |ab × ac|² = |ab|²|ac|² - (ab · ac)², holds in any dimension
+        let (ab, ac) = (self.b - self.a, self.c - self.a);
+        let dot = ab.dot(ac);
+        let cross_sqr = ab.len_sqr() * ac.len_sqr() - dot * dot;
+        (if cross_sqr > T::zero() { cross_sqr } else { T::zero() }).sqrt() / small(2)
+    }
+
+    /// Ericson, "Real-Time Collision Detection", 5.1.5; only uses dot products, so it works in any dimension
+    #[must_use]
+    pub fn closest_point(&self, point: Vector<T, N>) -> Vector<T, N> {
+        let Self { a, b, c } = *self;
+        let (ab, ac) = (b - a, c - a);
+
+        let ap = point - a;
+        let (d1, d2) = (ab.dot(ap), ac.dot(ap));
+        if d1 <= T::zero() && d2 <= T::zero() { return a; }
+
+        let bp = point - b;
+        let (d3, d4) = (ab.dot(bp), ac.dot(bp));
+        if d3 >= T::zero() && d4 <= d3 { return b; }
+
+        let vc = d1 * d4 - d3 * d2;
+        if vc <= T::zero() && d1 >= T::zero() && d3 <= T::zero() { return a + ab * (d1 / (d1 - d3)); }
+
+        let cp = point - c;
+        let (d5, d6) = (ab.dot(cp), ac.dot(cp));
+        if d6 >= T::zero() && d5 <= d6 { return c; }
+
+        let vb = d5 * d2 - d1 * d6;
+        if vb <= T::zero() && d2 >= T::zero() && d6 <= T::zero() { return a + ac * (d2 / (d2 - d6)); }
+
+        let va = d3 * d6 - d5 * d4;
+        if va <= T::zero() && d4 >= d3 && d5 >= d6 { return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6))); }
+
+        let denom = (va + vb + vc).recip();
+        a + ab * (vb * denom) + ac * (vc * denom)
+    }
+}
+impl<T: Real> Triangle<T, 3> {
+    /// Unit normal, facing the side the corners appear counter-clockwise from
+    #[must_use]
+    pub fn normal(&self) -> Vector<T, 3> { (self.b - self.a).cross(self.c - self.a).norm() }
+    #[must_use]
+    pub fn plane(&self) -> Plane<T> { Plane::from_point_normal(self.a, self.normal()) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+
+    #[test]
+    fn test_aabb() {
+        let b = Aabb::new(IVec3::new(4, 0, 1), IVec3::new(0, 2, 3));
+        assert_eq!((b.min, b.max), (IVec3::new(0, 0, 1), IVec3::new(4, 2, 3)));
+        assert_eq!((b.center(), b.size(), b.half_extents()), (IVec3::new(2, 1, 2), IVec3::new(4, 2, 2), IVec3::new(2, 1, 1)));
+        assert_eq!((b.volume(), b.surface_area()), (16, 2 * (8 + 4 + 8)));
+        assert_eq!(Aabb::from_center_half_extents(b.center(), b.half_extents()), b);
+        assert_eq!(Aabb::new(IVec2::new(0, 0), IVec2::new(3, 2)).perimeter(), 10);
+
+        assert!(b.contains_point(IVec3::new(4, 0, 2)) && !b.contains_point(IVec3::new(5, 0, 2)));
+        assert!(b.contains(&b) && b.contains(&b.grow(-1)) && !b.contains(&b.grow(1)));
+        assert_eq!(b.grow(1).volume(), 6 * 4 * 4);
+
+        let other = Aabb::new(IVec3::new(-1, 1, 2), IVec3::new(1, 5, 2));
+        let both = b.union(other);
+        assert_eq!((both.min, both.max), (IVec3::new(-1, 0, 1), IVec3::new(4, 5, 3)));
+        assert!(both.contains(&b) && both.contains(&other));
+        assert_eq!(b.expand_to(IVec3::new(-2, 1, 9)), Aabb::new(IVec3::new(-2, 0, 1), IVec3::new(4, 2, 9)));
+
+        let points = [IVec2::new(1, 5), IVec2::new(-2, 3), IVec2::new(0, 7)];
+        assert_eq!(Aabb::from_points(points), Some(Aabb::new(IVec2::new(-2, 3), IVec2::new(1, 7))));
+        assert_eq!(Aabb::<i32, 2>::from_points([]), None);
+
+        assert_eq!(b.closest_point(IVec3::new(2, 1, 2)), IVec3::new(2, 1, 2));
+        assert_eq!(b.closest_point(IVec3::new(7, -1, 2)), IVec3::new(4, 0, 2));
+        assert_eq!(b.dist_sqr(IVec3::new(7, -1, 2)), 9 + 1);
+    }
+
+    #[test]
+    fn test_ray_segment() {
+        let ray = Ray::through(Vec2::new(1.0, 1.0), Vec2::new(4.0, 5.0));
+        assert_eq!(ray.dir, Vec2::new(0.6, 0.8));
+        assert_eq!(ray.at(5.0), Vec2::new(4.0, 5.0));
+        assert_approx_eq!(ray.closest_point(Vec2::new(8.0, 1.0)), Vec2::new(3.52, 4.36), epsilon = 1e-5);
+        // behind the origin
+        assert_eq!(ray.closest_point(Vec2::new(-5.0, 1.0)), ray.origin);
+
+        let seg = Segment::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(4.0, 0.0, 0.0));
+        assert_eq!((seg.len(), seg.len_sqr(), seg.midpoint()), (4.0, 16.0, Vec3::new(2.0, 0.0, 0.0)));
+        assert_eq!(seg.closest_point(Vec3::new(1.0, 3.0, -2.0)), Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(seg.closest_point(Vec3::new(9.0, 1.0, 0.0)), seg.b);
+        assert_eq!(seg.closest_point(Vec3::new(-1.0, 1.0, 0.0)), seg.a);
+        assert_eq!(Segment::new(seg.a, seg.a).closest_point(Vec3::one()), seg.a);
+        assert_eq!(seg.aabb(), Aabb::new(seg.a, seg.b));
+    }
+
+    #[test]
+    fn test_sphere() {
+        let s = Sphere::new(Vec3::new(1.0, 2.0, 3.0), 2.0);
+        assert!(s.contains_point(Vec3::new(1.0, 4.0, 3.0)) && !s.contains_point(Vec3::new(1.0, 4.1, 3.0)));
+        assert_eq!(s.aabb(), Aabb::new(Vec3::new(-1.0, 0.0, 1.0), Vec3::new(3.0, 4.0, 5.0)));
+        assert_eq!(s.closest_point(Vec3::new(1.0, 2.0, 10.0)), Vec3::new(1.0, 2.0, 5.0));
+        assert_eq!(s.closest_point(Vec3::new(1.5, 2.0, 3.0)), Vec3::new(1.5, 2.0, 3.0));
+        assert!((s.volume() - 32.0 / 3.0 * std::f32::consts::PI).abs() < 1e-5);
+        assert!((s.surface_area() - 16.0 * std::f32::consts::PI).abs() < 1e-5);
+        let disk = Sphere::new(DVec2::zero(), 3.0);
+        assert_eq!((disk.area(), disk.circumference()), (9.0 * std::f64::consts::PI, 6.0 * std::f64::consts::PI));
+
+        let inner = Sphere::new(Vec3::new(1.0, 2.0, 4.0), 1.0);
+        assert!(s.contains(&inner) && !inner.contains(&s));
+        assert_eq!(s.union(inner), s);
+        assert_eq!(inner.union(s), s);
+
+        let a = Sphere::new(Vec2::new(0.0, 0.0), 1.0);
+        let b = Sphere::new(Vec2::new(4.0, 0.0), 2.0);
+        let both = a.union(b);
+        assert_eq!(both, Sphere::new(Vec2::new(2.5, 0.0), 3.5));
+        assert!(both.contains(&a) && both.contains(&b));
+        assert_eq!(a.expand_to(Vec2::new(0.0, 3.0)), Sphere::new(Vec2::new(0.0, 1.0), 2.0));
+    }
+
+    #[test]
+    fn test_plane() {
+        let p = Plane::from_points(Vec3::new(0.0, 0.0, 2.0), Vec3::new(1.0, 0.0, 2.0), Vec3::new(0.0, 1.0, 2.0));
+        assert_eq!((p.normal, p.d), (Vec3::new(0.0, 0.0, 1.0), 2.0));
+        assert_eq!(p.signed_dist(Vec3::new(5.0, 5.0, 5.0)), 3.0);
+        assert_eq!(p.signed_dist(Vec3::new(5.0, 5.0, -1.0)), -3.0);
+        assert_eq!(p.closest_point(Vec3::new(5.0, -4.0, -1.0)), Vec3::new(5.0, -4.0, 2.0));
+        assert_eq!(Plane::from_point_normal(Vec3::new(0.0, 3.0, 0.0), Vec3::new(0.0, 1.0, 0.0)), Plane::new(Vec3::new(0.0, 1.0, 0.0), 3.0));
+    }
+
+    #[test]
+    fn test_triangle() {
+        let t = Triangle::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(4.0, 0.0, 0.0), Vec3::new(0.0, 3.0, 0.0));
+        assert_eq!((t.area(), t.normal()), (6.0, Vec3::new(0.0, 0.0, 1.0)));
+        assert_eq!(t.plane(), Plane::new(Vec3::new(0.0, 0.0, 1.0), 0.0));
+        assert_eq!(t.centroid(), Vec3::new(4.0 / 3.0, 1.0, 0.0));
+        assert_eq!(t.aabb(), Aabb::new(Vec3::zero(), Vec3::new(4.0, 3.0, 0.0)));
+        assert_eq!(Triangle::new(Vec2::new(0.0, 0.0), Vec2::new(2.0, 0.0), Vec2::new(5.0, 2.0)).area(), 2.0);
+
+        // each Voronoi region: corners, edges, face
+        assert_eq!(t.closest_point(Vec3::new(-1.0, -1.0, 5.0)), t.a);
+        assert_eq!(t.closest_point(Vec3::new(6.0, -1.0, 0.0)), t.b);
+        assert_eq!(t.closest_point(Vec3::new(-1.0, 5.0, 0.0)), t.c);
+        assert_eq!(t.closest_point(Vec3::new(2.0, -3.0, 1.0)), Vec3::new(2.0, 0.0, 0.0));
+        assert_eq!(t.closest_point(Vec3::new(-3.0, 1.0, 1.0)), Vec3::new(0.0, 1.0, 0.0));
+        assert_eq!(t.closest_point(Vec3::new(1.0, 1.0, -7.0)), Vec3::new(1.0, 1.0, 0.0));
+        assert_approx_eq!(t.closest_point(Vec3::new(4.0, 3.0, 0.0)), Vec3::new(2.56, 1.08, 0.0));
+
+        // against a dense sampling of the triangle
+        for i in 0..50 {
+            let p = Vec3::new((i as f32 * 0.7).sin() * 6.0, (i as f32 * 1.3).cos() * 5.0, (i as f32 * 0.3).sin() * 2.0);
+            let closest = t.closest_point(p).dist(p);
+            let sampled = (0..=100).flat_map(|u| (0..=100 - u).map(move |v| (u as f32 / 100.0, v as f32 / 100.0)))
+                .map(|(u, v)| t.a + (t.b - t.a) * u + (t.c - t.a) * v)
+                .map(|q| q.dist(p))
+                .fold(f32::INFINITY, f32::min);
+            assert!(closest <= sampled + 1e-5 && sampled - closest < 0.05, "{p:?}");
+        }
+    }
+}
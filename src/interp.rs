@@ -0,0 +1,214 @@
+//! Interpolation between scalars and between [`Vector`]s
+//!
+//! Vector implementations work component-wise, except for [`MoveTowards`] and [`Slerp`] which
+//! treat the vector as a whole.
+
+use crate::{num::*, vec::*};
+
+/// `n` as any number, without a lossy cast
+#[inline]
+pub(crate) fn small<T: Num>(n: u8) -> T {
+    (0..n).fold(T::zero(), #[inline] |acc, _| acc + T::one())
+}
+
+/// Linear interpolation from `self` (at `t = 0`) to `other` (at `t = 1`)
+pub trait Lerp<T> {
+    type Output;
+    #[must_use]
+    fn lerp(self, other: Self, t: T) -> Self::Output;
+}
+impl<T: Real> Lerp<T> for T {
+    type Output = T;
+    #[inline]
+    fn lerp(self, other: Self, t: T) -> Self::Output { self + (other - self) * t }
+}
+impl<T: Real, const N: usize> Lerp<T> for Vector<T, N> {
+    type Output = Self;
+    #[inline]
+    fn lerp(self, other: Self, t: T) -> Self::Output { self + (other - self) * t }
+}
+impl<T: Real, const N: usize> Lerp<T> for &Vector<T, N> {
+    type Output = Vector<T, N>;
+    #[inline]
+    fn lerp(self, other: Self, t: T) -> Self::Output { (*self).lerp(*other, t) }
+}
+
+/// The `t` for which `self.lerp(other, t) == value`
+pub trait InverseLerp {
+    type Output;
+    #[must_use]
+    fn inverse_lerp(self, other: Self, value: Self) -> Self::Output;
+}
+impl<T: Real> InverseLerp for T {
+    type Output = T;
+    #[inline]
+    fn inverse_lerp(self, other: Self, value: Self) -> Self::Output { (value - self) / (other - self) }
+}
+impl<T: Real, const N: usize> InverseLerp for Vector<T, N> {
+    type Output = Self;
+    #[inline]
+    fn inverse_lerp(self, other: Self, value: Self) -> Self::Output { (value - self) / (other - self) }
+}
+impl<T: Real, const N: usize> InverseLerp for &Vector<T, N> {
+    type Output = Vector<T, N>;
+    #[inline]
+    fn inverse_lerp(self, other: Self, value: Self) -> Self::Output { (*self).inverse_lerp(*other, *value) }
+}
+
+/// Map `self` from the range `in_lo..in_hi` to the range `out_lo..out_hi`, without clamping
+pub trait Remap {
+    type Output;
+    #[must_use]
+    fn remap(self, in_lo: Self, in_hi: Self, out_lo: Self, out_hi: Self) -> Self::Output;
+}
+impl<T: Real> Remap for T {
+    type Output = T;
+    #[inline]
+    fn remap(self, in_lo: Self, in_hi: Self, out_lo: Self, out_hi: Self) -> Self::Output { out_lo + (out_hi - out_lo) * (self - in_lo) / (in_hi - in_lo) }
+}
+impl<T: Real, const N: usize> Remap for Vector<T, N> {
+    type Output = Self;
+    #[inline]
+    fn remap(self, in_lo: Self, in_hi: Self, out_lo: Self, out_hi: Self) -> Self::Output { out_lo + (out_hi - out_lo) * (self - in_lo) / (in_hi - in_lo) }
+}
+impl<T: Real, const N: usize> Remap for &Vector<T, N> {
+    type Output = Vector<T, N>;
+    #[inline]
+    fn remap(self, in_lo: Self, in_hi: Self, out_lo: Self, out_hi: Self) -> Self::Output { (*self).remap(*in_lo, *in_hi, *out_lo, *out_hi) }
+}
+
+/// Hermite easing of `self` between `edge0` and `edge1`, clamped to `[0, 1]`
+pub trait SmoothStep {
+    type Output;
+    /// `3t² - 2t³`, with zero slope at both edges
+    #[must_use]
+    fn smoothstep(self, edge0: Self, edge1: Self) -> Self::Output;
+    /// `6t⁵ - 15t⁴ + 10t³`, with zero slope and curvature at both edges
+    #[must_use]
+    fn smootherstep(self, edge0: Self, edge1: Self) -> Self::Output;
+}
+impl<T: Real> SmoothStep for T {
+    type Output = T;
+    #[inline]
+    fn smoothstep(self, edge0: Self, edge1: Self) -> Self::Output {
+        let t = edge0.inverse_lerp(edge1, self);
+        let t = if t < T::zero() { T::zero() } else if t > T::one() { T::one() } else { t };
+        t * t * (small::<T>(3) - small::<T>(2) * t)
+    }
+    #[inline]
+    fn smootherstep(self, edge0: Self, edge1: Self) -> Self::Output {
+        let t = edge0.inverse_lerp(edge1, self);
+        let t = if t < T::zero() { T::zero() } else if t > T::one() { T::one() } else { t };
+        t * t * t * (t * (t * small::<T>(6) - small::<T>(15)) + small::<T>(10))
+    }
+}
+impl<T: Real, const N: usize> SmoothStep for Vector<T, N> {
+    type Output = Self;
+    #[inline]
+    fn smoothstep(self, edge0: Self, edge1: Self) -> Self::Output { Vector(std::array::from_fn(#[inline] |i| self.0[i].smoothstep(edge0.0[i], edge1.0[i]))) }
+    #[inline]
+    fn smootherstep(self, edge0: Self, edge1: Self) -> Self::Output { Vector(std::array::from_fn(#[inline] |i| self.0[i].smootherstep(edge0.0[i], edge1.0[i]))) }
+}
+impl<T: Real, const N: usize> SmoothStep for &Vector<T, N> {
+    type Output = Vector<T, N>;
+    #[inline]
+    fn smoothstep(self, edge0: Self, edge1: Self) -> Self::Output { (*self).smoothstep(*edge0, *edge1) }
+    #[inline]
+    fn smootherstep(self, edge0: Self, edge1: Self) -> Self::Output { (*self).smootherstep(*edge0, *edge1) }
+}
+
+/// Step from `self` towards `target` by at most `max_delta`, without overshooting
+pub trait MoveTowards<T> {
+    type Output;
+    #[must_use]
+    fn move_towards(self, target: Self, max_delta: T) -> Self::Output;
+}
+impl<T: Real> MoveTowards<T> for T {
+    type Output = T;
+    #[inline]
+    fn move_towards(self, target: Self, max_delta: T) -> Self::Output {
+        let delta = target - self;
+        if delta.abs() <= max_delta { target } else { self + delta.signum() * max_delta }
+    }
+}
+impl<T: Real, const N: usize> MoveTowards<T> for Vector<T, N> {
+    type Output = Self;
+    #[inline]
+    fn move_towards(self, target: Self, max_delta: T) -> Self::Output {
+        let delta = target - self;
+        let dist = delta.len();
+        if dist <= max_delta || dist.is_zero() { target } else { self + delta * (max_delta / dist) }
+    }
+}
+impl<T: Real, const N: usize> MoveTowards<T> for &Vector<T, N> {
+    type Output = Vector<T, N>;
+    #[inline]
+    fn move_towards(self, target: Self, max_delta: T) -> Self::Output { (*self).move_towards(*target, max_delta) }
+}
+
+/// Spherical interpolation between unit vectors, at constant angular speed.
+/// The vectors must not point in opposite directions.
+pub trait Slerp<T> {
+    type Output;
+    #[must_use]
+    fn slerp(self, other: Self, t: T) -> Self::Output;
+}
+impl<T: Real, const N: usize> Slerp<T> for Vector<T, N> {
+    type Output = Self;
+    #[inline]
+    fn slerp(self, other: Self, t: T) -> Self::Output {
+        let d = self.dot(other);
+        let d = if d > T::one() { T::one() } else if d < -T::one() { -T::one() } else { d };
+        let ortho = other - self * d;
+        if ortho.len_sqr() <= T::EPSILON {
+            // nearly parallel; the orthogonal part is too small to normalize
+            return self.lerp(other, t).norm();
+        }
+        let (sin, cos) = (d.acos() * t).sin_cos();
+        self * cos + ortho.norm() * sin
+    }
+}
+impl<T: Real, const N: usize> Slerp<T> for &Vector<T, N> {
+    type Output = Vector<T, N>;
+    #[inline]
+    fn slerp(self, other: Self, t: T) -> Self::Output { (*self).slerp(*other, t) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scalar() {
+        assert_eq!(2.0_f32.lerp(6.0, 0.25), 3.0);
+        assert_eq!(2.0_f32.inverse_lerp(6.0, 3.0), 0.25);
+        assert_eq!(5.0_f64.remap(0.0, 10.0, -1.0, 1.0), 0.0);
+        assert_eq!(0.5_f32.smoothstep(0.0, 1.0), 0.5);
+        assert_eq!(2.0_f32.smoothstep(0.0, 1.0), 1.0);
+        assert_eq!(0.25_f64.smootherstep(0.0, 1.0), 0.103515625);
+        assert_eq!(1.0_f32.move_towards(5.0, 3.0), 4.0);
+        assert_eq!(1.0_f32.move_towards(-1.0, 3.0), -1.0);
+    }
+
+    #[test]
+    fn test_vector() {
+        let (a, b) = (Vec2::new(0.0, 2.0), Vec2::new(4.0, 6.0));
+        assert_eq!(a.lerp(b, 0.5), Vec2::new(2.0, 4.0));
+        assert_eq!(a.inverse_lerp(b, Vec2::new(1.0, 5.0)), Vec2::new(0.25, 0.75));
+        assert_eq!(Vec2::new(1.0, 5.0).remap(a, b, Vec2::zero(), Vec2::one()), Vec2::new(0.25, 0.75));
+        assert_eq!(Vec2::new(-1.0, 4.0).smoothstep(a, b), Vec2::new(0.0, 0.5));
+        assert_eq!(Vec2::new(0.0, 0.0).move_towards(Vec2::new(3.0, 4.0), 2.5), Vec2::new(1.5, 2.0));
+        assert_eq!(Vec2::new(0.0, 0.0).move_towards(Vec2::new(3.0, 4.0), 10.0), Vec2::new(3.0, 4.0));
+    }
+
+    #[test]
+    fn test_slerp() {
+        let (x, y) = (Vector::<f64, 3>::new(1.0, 0.0, 0.0), Vector::<f64, 3>::new(0.0, 1.0, 0.0));
+        let mid = x.slerp(y, 0.5);
+        assert!((mid - Vector::<f64, 3>::new(1.0, 1.0, 0.0).norm()).len() < 1e-12);
+        let third = x.slerp(y, 1.0 / 3.0);
+        assert!((third.dot(x) - std::f64::consts::FRAC_PI_6.cos()).abs() < 1e-12);
+        assert!((third.len() - 1.0).abs() < 1e-12);
+        assert_eq!(x.slerp(x, 0.5), x);
+    }
+}
@@ -1,12 +1,35 @@
 pub mod meta;
+pub mod num;
+pub mod fixed;
 pub mod vec;
+pub mod mat;
+pub mod quat;
+pub mod affine;
+pub mod camera;
+pub mod interp;
+pub mod angle;
+pub mod approx_eq;
 pub mod math;
+pub mod geom;
+pub mod batch;
 pub mod containers;
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+mod simd;
 
 pub mod prelude {
     pub use crate::{
+        num::*,
+        fixed::*,
         vec::*,
+        mat::*,
+        quat::*,
+        affine::*,
+        camera::*,
+        interp::*,
+        angle::*,
+        approx_eq::*,
         math::*,
+        geom::{*, intersect::*},
         containers::{
             multi_vec::*,
         },
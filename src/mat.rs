@@ -0,0 +1,274 @@
+use std::{fmt, hash::Hash, ops::*};
+use crate::{num::*, vec::*};
+
+/// An `R`x`C` matrix, stored as `R` row [`Vector`]s of length `C`
+pub struct Matrix<T, const R: usize, const C: usize>(pub(crate) [Vector<T, C>; R]);
+
+impl<T: fmt::Debug, const R: usize, const C: usize> fmt::Debug for Matrix<T, R, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut d = f.debug_tuple("Matrix");
+        for row in &self.0 { d.field(row); }
+        d.finish()
+    }
+}
+
+impl<T: Clone, const R: usize, const C: usize> Clone for Matrix<T, R, C> { fn clone(&self) -> Self { Self(self.0.clone()) } }
+impl<T: Copy, const R: usize, const C: usize> Copy for Matrix<T, R, C> {}
+impl<T: PartialEq, const R: usize, const C: usize> PartialEq for Matrix<T, R, C> { fn eq(&self, other: &Self) -> bool { self.0 == other.0 } }
+impl<T: Eq, const R: usize, const C: usize> Eq for Matrix<T, R, C> {}
+impl<T: Hash, const R: usize, const C: usize> Hash for Matrix<T, R, C> { fn hash<H: std::hash::Hasher>(&self, state: &mut H) { self.0.hash(state); } }
+
+impl<T: Copy + Neg<Output = T>, const R: usize, const C: usize> Neg for Matrix<T, R, C> { type Output = Self; fn neg(self) -> Self::Output { Self(self.0.map(#[inline] |row| row.neg())) } }
+impl<T: Copy + Add<Output = T>, const R: usize, const C: usize> Add for Matrix<T, R, C> { type Output = Self; fn add(self, rhs: Self) -> Self::Output { Self(std::array::from_fn(#[inline] |i| self.0[i].add(rhs.0[i]))) } }
+impl<T: Copy + Sub<Output = T>, const R: usize, const C: usize> Sub for Matrix<T, R, C> { type Output = Self; fn sub(self, rhs: Self) -> Self::Output { Self(std::array::from_fn(#[inline] |i| self.0[i].sub(rhs.0[i]))) } }
+
+impl<T: Copy + Neg<Output = T>, const R: usize, const C: usize> Neg for &Matrix<T, R, C> { type Output = Matrix<T, R, C>; fn neg(self) -> Self::Output { Matrix(self.0.map(#[inline] |row| row.neg())) } }
+impl<T: Copy + Add<Output = T>, const R: usize, const C: usize> Add for &Matrix<T, R, C> { type Output = Matrix<T, R, C>; fn add(self, rhs: Self) -> Self::Output { Matrix(std::array::from_fn(#[inline] |i| self.0[i].add(rhs.0[i]))) } }
+impl<T: Copy + Sub<Output = T>, const R: usize, const C: usize> Sub for &Matrix<T, R, C> { type Output = Matrix<T, R, C>; fn sub(self, rhs: Self) -> Self::Output { Matrix(std::array::from_fn(#[inline] |i| self.0[i].sub(rhs.0[i]))) } }
+
+impl<T: Copy + Mul<Output = T>, const R: usize, const C: usize> Mul<T> for Matrix<T, R, C> { type Output = Self; fn mul(self, rhs: T) -> Self::Output { Self(self.0.map(#[inline] |row| row.mul(rhs))) } }
+impl<T: Copy + ParallelDiv, const R: usize, const C: usize> Div<T> for Matrix<T, R, C> { type Output = Self; fn div(self, rhs: T) -> Self::Output { let denom_or_recip = rhs.denom_or_recip(); Self(self.0.map(#[inline] |row| Vector(row.0.map(#[inline] |comp| comp.parallel_div(denom_or_recip))))) } }
+
+impl<T: Copy + Mul<Output = T>, const R: usize, const C: usize> Mul<T> for &Matrix<T, R, C> { type Output = Matrix<T, R, C>; fn mul(self, rhs: T) -> Self::Output { Matrix(self.0.map(#[inline] |row| row.mul(rhs))) } }
+impl<T: Copy + ParallelDiv, const R: usize, const C: usize> Div<T> for &Matrix<T, R, C> { type Output = Matrix<T, R, C>; fn div(self, rhs: T) -> Self::Output { let denom_or_recip = rhs.denom_or_recip(); Matrix(self.0.map(#[inline] |row| Vector(row.0.map(#[inline] |comp| comp.parallel_div(denom_or_recip))))) } }
+
+impl<T: Num, const R: usize, const K: usize, const C: usize> Mul<Matrix<T, K, C>> for Matrix<T, R, K> {
+    type Output = Matrix<T, R, C>;
+    fn mul(self, rhs: Matrix<T, K, C>) -> Self::Output {
+        let rhs_t = rhs.transpose();
+        Matrix(self.0.map(#[inline] |row| Vector(rhs_t.0.map(#[inline] |col| row.dot(col)))))
+    }
+}
+impl<T: Num, const R: usize, const K: usize, const C: usize> Mul<&Matrix<T, K, C>> for &Matrix<T, R, K> {
+    type Output = Matrix<T, R, C>;
+    fn mul(self, rhs: &Matrix<T, K, C>) -> Self::Output { *self * *rhs }
+}
+
+impl<T: Num, const R: usize, const C: usize> Mul<Vector<T, C>> for Matrix<T, R, C> {
+    type Output = Vector<T, R>;
+    fn mul(self, rhs: Vector<T, C>) -> Self::Output { Vector(self.0.map(#[inline] |row| row.dot(rhs))) }
+}
+impl<T: Num, const R: usize, const C: usize> Mul<&Vector<T, C>> for &Matrix<T, R, C> {
+    type Output = Vector<T, R>;
+    fn mul(self, rhs: &Vector<T, C>) -> Self::Output { *self * *rhs }
+}
+
+impl<T, const R: usize, const C: usize> From<[[T; C]; R]> for Matrix<T, R, C> { fn from(value: [[T; C]; R]) -> Self { Self(value.map(Vector)) } }
+impl<T, const R: usize, const C: usize> From<Matrix<T, R, C>> for [[T; C]; R] { fn from(value: Matrix<T, R, C>) -> Self { value.0.map(<[T; C]>::from) } }
+impl<T, const R: usize, const C: usize> From<[Vector<T, C>; R]> for Matrix<T, R, C> { fn from(value: [Vector<T, C>; R]) -> Self { Self(value) } }
+impl<T, const R: usize, const C: usize> From<Matrix<T, R, C>> for [Vector<T, C>; R] { fn from(value: Matrix<T, R, C>) -> Self { value.0 } }
+
+// general definition
+impl<T, const R: usize, const C: usize> Matrix<T, R, C> {
+    pub const fn from_rows(rows: [Vector<T, C>; R]) -> Self { Self(rows) }
+
+    pub const fn row(&self, index: usize) -> &Vector<T, C> { &self.0[index] }
+    pub fn row_mut(&mut self, index: usize) -> &mut Vector<T, C> { &mut self.0[index] }
+
+    pub const fn elem(&self, row: usize, col: usize) -> &T { self.0[row].comp(col) }
+    pub fn elem_mut(&mut self, row: usize, col: usize) -> &mut T { self.0[row].comp_mut(col) }
+}
+
+impl<T: Copy, const R: usize, const C: usize> Matrix<T, R, C> {
+    pub fn from_cols(cols: [Vector<T, R>; C]) -> Self { Matrix(cols).transpose() }
+
+    pub fn col(&self, index: usize) -> Vector<T, R> { Vector(self.0.map(#[inline] |row| row.0[index])) }
+
+    /// Swap rows with columns
+    #[must_use]
+    pub fn transpose(&self) -> Matrix<T, C, R> { Matrix(std::array::from_fn(#[inline] |j| self.col(j))) }
+}
+
+impl<T: Copy + Zero + One, const N: usize> Matrix<T, N, N> {
+    /// Ones on the diagonal, zeros everywhere else
+    pub fn identity() -> Self { Self(std::array::from_fn(#[inline] |i| Vector(std::array::from_fn(#[inline] |j| if i == j { T::one() } else { T::zero() })))) }
+
+    pub fn diagonal(&self) -> Vector<T, N> { Vector(std::array::from_fn(#[inline] |i| self.0[i].0[i])) }
+}
+
+// 2x2
+impl<T> Matrix<T, 2, 2> {
+    pub const fn new(m00: T, m01: T, m10: T, m11: T) -> Self { Self([Vector([m00, m01]), Vector([m10, m11])]) }
+}
+impl<T: Num> Matrix<T, 2, 2> {
+    #[must_use]
+    pub fn determinant(&self) -> T {
+        let [Vector([a, b]), Vector([c, d])] = self.0;
+        a * d - b * c
+    }
+}
+impl<T: Real> Matrix<T, 2, 2> {
+    /// [`None`] if the matrix is singular. Integer matrices have no inverse, since it would truncate:
+    ///
+    /// ```compile_fail
+    /// # use amy_math::prelude::*;
+    /// let _ = Matrix::<i32, 2, 2>::new(2, 0, 0, 2).inverse();
+    /// ```
+    #[must_use]
+    pub fn inverse(&self) -> Option<Self> {
+        let det = self.determinant();
+        if det.is_zero() { return None; }
+        let [Vector([a, b]), Vector([c, d])] = self.0;
+        Some(Self::new(d, -b, -c, a) / det)
+    }
+}
+pub type Mat2 = Matrix<f32, 2, 2>;
+
+// 3x3
+impl<T> Matrix<T, 3, 3> {
+    #[allow(clippy::too_many_arguments)]
+    pub const fn new(
+        m00: T, m01: T, m02: T,
+        m10: T, m11: T, m12: T,
+        m20: T, m21: T, m22: T,
+    ) -> Self {
+        Self([
+            Vector([m00, m01, m02]),
+            Vector([m10, m11, m12]),
+            Vector([m20, m21, m22]),
+        ])
+    }
+}
+impl<T: Num> Matrix<T, 3, 3> {
+    #[must_use]
+    pub fn determinant(&self) -> T {
+        let [Vector([a, b, c]), Vector([d, e, f]), Vector([g, h, i])] = self.0;
+        a * (e * i - f * h) - b * (d * i - f * g) + c * (d * h - e * g)
+    }
+}
+impl<T: Real> Matrix<T, 3, 3> {
+    /// [`None`] if the matrix is singular. Integer matrices have no inverse, since it would truncate:
+    ///
+    /// ```compile_fail
+    /// # use amy_math::prelude::*;
+    /// let _ = Matrix::<i32, 3, 3>::new(2, 0, 0, 0, 2, 0, 0, 0, 2).inverse();
+    /// ```
+    #[must_use]
+    pub fn inverse(&self) -> Option<Self> {
+        let det = self.determinant();
+        if det.is_zero() { return None; }
+        let [Vector([a, b, c]), Vector([d, e, f]), Vector([g, h, i])] = self.0;
+        Some(Self::new(
+            e * i - f * h, c * h - b * i, b * f - c * e,
+            f * g - d * i, a * i - c * g, c * d - a * f,
+            d * h - e * g, b * g - a * h, a * e - b * d,
+        ) / det)
+    }
+}
+pub type Mat3 = Matrix<f32, 3, 3>;
+
+// 4x4
+impl<T> Matrix<T, 4, 4> {
+    #[allow(clippy::too_many_arguments)]
+    pub const fn new(
+        m00: T, m01: T, m02: T, m03: T,
+        m10: T, m11: T, m12: T, m13: T,
+        m20: T, m21: T, m22: T, m23: T,
+        m30: T, m31: T, m32: T, m33: T,
+    ) -> Self {
+        Self([
+            Vector([m00, m01, m02, m03]),
+            Vector([m10, m11, m12, m13]),
+            Vector([m20, m21, m22, m23]),
+            Vector([m30, m31, m32, m33]),
+        ])
+    }
+}
+impl<T: Num> Matrix<T, 4, 4> {
+    /// 2x2 minors of the top two rows and of the bottom two rows
+    fn sub_factors(&self) -> ([T; 6], [T; 6]) {
+        let [
+            Vector([a00, a01, a02, a03]),
+            Vector([a10, a11, a12, a13]),
+            Vector([a20, a21, a22, a23]),
+            Vector([a30, a31, a32, a33]),
+        ] = self.0;
+        (
+            [
+                a00 * a11 - a10 * a01,
+                a00 * a12 - a10 * a02,
+                a00 * a13 - a10 * a03,
+                a01 * a12 - a11 * a02,
+                a01 * a13 - a11 * a03,
+                a02 * a13 - a12 * a03,
+            ],
+            [
+                a20 * a31 - a30 * a21,
+                a20 * a32 - a30 * a22,
+                a20 * a33 - a30 * a23,
+                a21 * a32 - a31 * a22,
+                a21 * a33 - a31 * a23,
+                a22 * a33 - a32 * a23,
+            ],
+        )
+    }
+
+    #[must_use]
+    pub fn determinant(&self) -> T {
+        let ([s0, s1, s2, s3, s4, s5], [c0, c1, c2, c3, c4, c5]) = self.sub_factors();
+        s0 * c5 - s1 * c4 + s2 * c3 + s3 * c2 - s4 * c1 + s5 * c0
+    }
+}
+impl<T: Real> Matrix<T, 4, 4> {
+    /// [`None`] if the matrix is singular. Integer matrices have no inverse, since it would truncate:
+    ///
+    /// ```compile_fail
+    /// # use amy_math::prelude::*;
+    /// let _ = Matrix::<i32, 4, 4>::new(2, 0, 0, 0, 0, 2, 0, 0, 0, 0, 2, 0, 0, 0, 0, 2).inverse();
+    /// ```
+    #[must_use]
+    pub fn inverse(&self) -> Option<Self> {
+        let ([s0, s1, s2, s3, s4, s5], [c0, c1, c2, c3, c4, c5]) = self.sub_factors();
+        let det = s0 * c5 - s1 * c4 + s2 * c3 + s3 * c2 - s4 * c1 + s5 * c0;
+        if det.is_zero() { return None; }
+        let [
+            Vector([a00, a01, a02, a03]),
+            Vector([a10, a11, a12, a13]),
+            Vector([a20, a21, a22, a23]),
+            Vector([a30, a31, a32, a33]),
+        ] = self.0;
+        Some(Self::new(
+             a11 * c5 - a12 * c4 + a13 * c3, -a01 * c5 + a02 * c4 - a03 * c3,  a31 * s5 - a32 * s4 + a33 * s3, -a21 * s5 + a22 * s4 - a23 * s3,
+            -a10 * c5 + a12 * c2 - a13 * c1,  a00 * c5 - a02 * c2 + a03 * c1, -a30 * s5 + a32 * s2 - a33 * s1,  a20 * s5 - a22 * s2 + a23 * s1,
+             a10 * c4 - a11 * c2 + a13 * c0, -a00 * c4 + a01 * c2 - a03 * c0,  a30 * s4 - a31 * s2 + a33 * s0, -a20 * s4 + a21 * s2 - a23 * s0,
+            -a10 * c3 + a11 * c1 - a12 * c0,  a00 * c3 - a01 * c1 + a02 * c0, -a30 * s3 + a31 * s1 - a32 * s0,  a20 * s3 - a21 * s1 + a22 * s0,
+        ) / det)
+    }
+}
+pub type Mat4 = Matrix<f32, 4, 4>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mul_vec() {
+        let m = Matrix::<i32, 2, 3>::from([[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(m * IVec3::new(1, 0, -1), IVec2::new(-2, -2));
+    }
+
+    #[test]
+    fn test_mul_mat() {
+        let a = Matrix::<i32, 2, 3>::from([[1, 2, 3], [4, 5, 6]]);
+        let b = a.transpose();
+        assert_eq!(a * b, Matrix::<i32, 2, 2>::new(14, 32, 32, 77));
+        assert_eq!(a * Matrix::identity(), a);
+    }
+
+    #[test]
+    fn test_inverse() {
+        let m = Matrix::<f64, 4, 4>::new(
+            2.0, 0.0, 0.0, 1.0,
+            0.0, 4.0, 0.0, 0.0,
+            0.0, 0.0, 8.0, 0.0,
+            0.0, 0.0, 0.0, 1.0,
+        );
+        assert_eq!(m.determinant(), 64.0);
+        assert_eq!(m * m.inverse().unwrap(), Matrix::identity());
+
+        let m = Matrix::<f64, 3, 3>::new(1.0, 2.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 2.0);
+        assert_eq!(m * m.inverse().unwrap(), Matrix::identity());
+
+        assert_eq!(Matrix::<f64, 2, 2>::new(1.0, 2.0, 2.0, 4.0).inverse(), None);
+    }
+}
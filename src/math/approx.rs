@@ -0,0 +1,337 @@
+//! Fast `f32` approximations of transcendental functions
+//!
+//! Every function comes in two precision tiers, [`fast`] and [`precise`], which only differ in
+//! how many polynomial terms or Newton steps they spend. Each also has a `_vec` variant mapping it
+//! over the components of a [`Vector<f32, N>`].
+//!
+//! Errors are measured as `|approx - exact| / max(1, |exact|)`: absolute for results in `[-1, 1]`,
+//! relative for anything larger. Inputs are assumed to be finite and, where it matters, normal.
+//! The trigonometric functions only meet their bound for `|x| <= 1e9`.
+
+// constants are written out to the digits they were derived with
+#![allow(clippy::excessive_precision)]
+
+use std::f32::consts::*;
+use crate::vec::Vector;
+
+/// Polynomial coefficients and iteration counts for one precision tier
+struct Tier {
+    /// `sin(r) / r` in powers of `r²`
+    sin: &'static [f32],
+    /// `cos(r)` in powers of `r²`
+    cos: &'static [f32],
+    /// `atan(t) / t` in powers of `t²`
+    atan: &'static [f32],
+    /// `exp(r)` in powers of `r`
+    exp: &'static [f32],
+    /// `atanh(s) / s` in powers of `s²`
+    atanh: &'static [f32],
+    /// Newton-Raphson steps refining the reciprocal estimate
+    rcp_steps: u32,
+}
+
+const FAST: Tier = Tier {
+    sin:   &[1.0, -1.0 / 6.0, 1.0 / 120.0],
+    cos:   &[1.0, -1.0 / 2.0, 1.0 / 24.0],
+    atan:  &[1.0, -1.0 / 3.0, 1.0 / 5.0],
+    exp:   &[1.0, 1.0, 1.0 / 2.0, 1.0 / 6.0, 1.0 / 24.0],
+    atanh: &[1.0, 1.0 / 3.0],
+    rcp_steps: 2,
+};
+
+const PRECISE: Tier = Tier {
+    sin:   &[1.0, -1.0 / 6.0, 1.0 / 120.0, -1.0 / 5040.0, 1.0 / 362880.0],
+    cos:   &[1.0, -1.0 / 2.0, 1.0 / 24.0, -1.0 / 720.0, 1.0 / 40320.0],
+    atan:  &[1.0, -1.0 / 3.0, 1.0 / 5.0, -1.0 / 7.0, 1.0 / 9.0, -1.0 / 11.0, 1.0 / 13.0],
+    exp:   &[1.0, 1.0, 1.0 / 2.0, 1.0 / 6.0, 1.0 / 24.0, 1.0 / 120.0, 1.0 / 720.0, 1.0 / 5040.0],
+    atanh: &[1.0, 1.0 / 3.0, 1.0 / 5.0, 1.0 / 7.0, 1.0 / 9.0],
+    rcp_steps: 3,
+};
+
+// π/2 split so that `k * PIO2_HI` is exact for `|k| < 2^20` (Cody-Waite), reducing in `f64`
+// as in musl's `__rem_pio2f`. The remaining rounding error grows with `|x|`.
+const PIO2_HI: f64 = 1.57079631090164184570e+00;
+const PIO2_LO: f64 = 1.58932547735281966916e-08;
+
+// ln(2) split the same way
+const LN2_HI: f32 =  0.693359375;
+const LN2_LO: f32 = -2.12194440e-4;
+
+const TAN_FRAC_PI_8: f32 = 0.41421357;
+
+#[inline]
+fn horner(x: f32, coeffs: &[f32]) -> f32 {
+    coeffs.iter().rev().fold(0.0, #[inline] |acc, &c| acc * x + c)
+}
+
+/// `2^k` for `k` in `-252..=254`, split in two so that neither factor leaves the normal range
+#[inline]
+fn pow2i(k: i32) -> f32 {
+    let half = k / 2;
+    let scale = |e: i32| f32::from_bits(((e + 127) as u32) << 23);
+    scale(half) * scale(k - half)
+}
+
+#[inline]
+fn sin_cos(x: f32, tier: &Tier) -> (f32, f32) {
+    let x = x as f64;
+    let k = (x * std::f64::consts::FRAC_2_PI).round();
+    let r = ((x - k * PIO2_HI) - k * PIO2_LO) as f32;
+    let r2 = r * r;
+    let (s, c) = (r * horner(r2, tier.sin), horner(r2, tier.cos));
+    match (k as i32) & 3 {
+        0 => ( s,  c),
+        1 => ( c, -s),
+        2 => (-s, -c),
+        _ => (-c,  s),
+    }
+}
+
+/// `atan(z)` for `z` in `[0, 1]`
+#[inline]
+fn atan01(z: f32, tier: &Tier) -> f32 {
+    let (offset, t) = if z > TAN_FRAC_PI_8 { (FRAC_PI_4, (z - 1.0) / (z + 1.0)) } else { (0.0, z) };
+    offset + t * horner(t * t, tier.atan)
+}
+
+#[inline]
+fn atan2(y: f32, x: f32, tier: &Tier) -> f32 {
+    let (ax, ay) = (x.abs(), y.abs());
+    if ax == 0.0 && ay == 0.0 {
+        return if x.is_sign_negative() { PI } else { 0.0 }.copysign(y);
+    }
+    let a = if ay > ax { FRAC_PI_2 - atan01(ax / ay, tier) } else { atan01(ay / ax, tier) };
+    let a = if x < 0.0 { PI - a } else { a };
+    a.copysign(y)
+}
+
+#[inline]
+fn exp(x: f32, tier: &Tier) -> f32 {
+    if x.is_nan() { return x; }
+    if x > 88.72284 { return f32::INFINITY; }
+    if x < -87.33655 { return 0.0; }
+    let k = (x * LOG2_E).round();
+    let r = (x - k * LN2_HI) - k * LN2_LO;
+    horner(r, tier.exp) * pow2i(k as i32)
+}
+
+#[inline]
+fn exp2(x: f32, tier: &Tier) -> f32 {
+    if x.is_nan() { return x; }
+    if x > 128.0 { return f32::INFINITY; }
+    if x < -126.0 { return 0.0; }
+    let k = x.round();
+    horner((x - k) * LN_2, tier.exp) * pow2i(k as i32)
+}
+
+/// `(e, ln(m))` where `x = m * 2^e`
+#[inline]
+fn log_parts(x: f32, tier: &Tier) -> (f32, f32) {
+    let (x, bias) = if x < f32::MIN_POSITIVE { (x * 8388608.0, -23) } else { (x, 0) };
+    let bits = x.to_bits();
+    let mut e = ((bits >> 23) as i32) - 127 + bias;
+    let mut m = f32::from_bits((bits & 0x007F_FFFF) | 0x3F80_0000);
+    if m > SQRT_2 {
+        m *= 0.5;
+        e += 1;
+    }
+    let s = (m - 1.0) / (m + 1.0);
+    (e as f32, 2.0 * s * horner(s * s, tier.atanh))
+}
+
+#[inline]
+fn log(x: f32, tier: &Tier) -> f32 {
+    if x.is_nan() || x < 0.0 { return f32::NAN; }
+    if x == 0.0 { return f32::NEG_INFINITY; }
+    if x == f32::INFINITY { return x; }
+    let (e, ln_m) = log_parts(x, tier);
+    e * LN_2 + ln_m
+}
+
+#[inline]
+fn log2(x: f32, tier: &Tier) -> f32 {
+    if x.is_nan() || x < 0.0 { return f32::NAN; }
+    if x == 0.0 { return f32::NEG_INFINITY; }
+    if x == f32::INFINITY { return x; }
+    let (e, ln_m) = log_parts(x, tier);
+    e + ln_m * LOG2_E
+}
+
+#[inline]
+fn pow(x: f32, y: f32, tier: &Tier) -> f32 {
+    if y == 0.0 || x == 1.0 { return 1.0; }
+    if x == 0.0 { return if y > 0.0 { 0.0 } else { f32::INFINITY }; }
+    exp2(y * log2(x, tier), tier)
+}
+
+#[inline]
+fn rcp(x: f32, tier: &Tier) -> f32 {
+    let ax = x.abs();
+    if ax == 0.0 { return f32::INFINITY.copysign(x); }
+    if ax == f32::INFINITY { return 0.0_f32.copysign(x); }
+    let mut y = f32::from_bits(0x7EF3_11C7 - ax.to_bits()); // bit-level first guess, like q_rsqrt
+    for _ in 0..tier.rcp_steps {
+        y = y * (2.0 - ax * y);
+    }
+    y.copysign(x)
+}
+
+macro_rules! impl_tier {
+    ($tier:ident = $TIER:ident: $($doc:literal)+) => {
+        $(#[doc = $doc])+
+        pub mod $tier {
+            use super::*;
+
+            /// Sine of `x` radians, for `|x| <= 1e9`
+            pub fn sin(x: f32) -> f32 { super::sin_cos(x, &$TIER).0 }
+            /// Cosine of `x` radians, for `|x| <= 1e9`
+            pub fn cos(x: f32) -> f32 { super::sin_cos(x, &$TIER).1 }
+            /// Sine and cosine of `x` radians, sharing the range reduction, for `|x| <= 1e9`
+            pub fn sin_cos(x: f32) -> (f32, f32) { super::sin_cos(x, &$TIER) }
+            /// Tangent of `x` radians, for `|x| <= 1e9`; the error is relative to `tan(x)`, so it grows near the poles
+            pub fn tan(x: f32) -> f32 { let (s, c) = super::sin_cos(x, &$TIER); s / c }
+            /// Four-quadrant arctangent of `y / x`, in `[-π, π]`
+            pub fn atan2(y: f32, x: f32) -> f32 { super::atan2(y, x, &$TIER) }
+            /// `e^x`
+            pub fn exp(x: f32) -> f32 { super::exp(x, &$TIER) }
+            /// `2^x`
+            pub fn exp2(x: f32) -> f32 { super::exp2(x, &$TIER) }
+            /// Natural logarithm
+            pub fn log(x: f32) -> f32 { super::log(x, &$TIER) }
+            /// Base 2 logarithm
+            pub fn log2(x: f32) -> f32 { super::log2(x, &$TIER) }
+            /// `x^y` for positive `x`, computed as `2^(y * log2(x))`.
+            /// The relative error is scaled by roughly `1 + |y * log2(x)|`.
+            pub fn pow(x: f32, y: f32) -> f32 { super::pow(x, y, &$TIER) }
+            /// `1 / x` without a division
+            pub fn rcp(x: f32) -> f32 { super::rcp(x, &$TIER) }
+
+            pub fn sin_vec <const N: usize>(v: Vector<f32, N>) -> Vector<f32, N> { Vector(v.0.map(sin)) }
+            pub fn cos_vec <const N: usize>(v: Vector<f32, N>) -> Vector<f32, N> { Vector(v.0.map(cos)) }
+            pub fn tan_vec <const N: usize>(v: Vector<f32, N>) -> Vector<f32, N> { Vector(v.0.map(tan)) }
+            pub fn exp_vec <const N: usize>(v: Vector<f32, N>) -> Vector<f32, N> { Vector(v.0.map(exp)) }
+            pub fn exp2_vec<const N: usize>(v: Vector<f32, N>) -> Vector<f32, N> { Vector(v.0.map(exp2)) }
+            pub fn log_vec <const N: usize>(v: Vector<f32, N>) -> Vector<f32, N> { Vector(v.0.map(log)) }
+            pub fn log2_vec<const N: usize>(v: Vector<f32, N>) -> Vector<f32, N> { Vector(v.0.map(log2)) }
+            pub fn rcp_vec <const N: usize>(v: Vector<f32, N>) -> Vector<f32, N> { Vector(v.0.map(rcp)) }
+            pub fn atan2_vec<const N: usize>(y: Vector<f32, N>, x: Vector<f32, N>) -> Vector<f32, N> { Vector(std::array::from_fn(#[inline] |i| atan2(y.0[i], x.0[i]))) }
+            pub fn pow_vec  <const N: usize>(x: Vector<f32, N>, y: Vector<f32, N>) -> Vector<f32, N> { Vector(std::array::from_fn(#[inline] |i| pow(x.0[i], y.0[i]))) }
+        }
+    };
+}
+
+impl_tier!(fast = FAST: "Maximum error of `1e-3`");
+impl_tier!(precise = PRECISE: "Maximum error of `1e-6`");
+
+#[cfg(test)]
+mod tests {
+    /// Largest `|approx - exact| / max(1, |exact|)` over `count` evenly spaced points in `[lo, hi]`
+    fn max_error(lo: f64, hi: f64, count: usize, approx: impl Fn(f32) -> f32, exact: impl Fn(f64) -> f64) -> f64 {
+        (0..=count)
+            .map(|i| {
+                let x = (lo + (hi - lo) * i as f64 / count as f64) as f32;
+                let expected = exact(x as f64);
+                (approx(x) as f64 - expected).abs() / expected.abs().max(1.0)
+            })
+            .fold(0.0, f64::max)
+    }
+
+    macro_rules! test_tier {
+        ($tier:ident, $tol:expr) => {
+            mod $tier {
+                use super::max_error;
+                use crate::{vec::Vector, math::approx::$tier::*};
+                const TOL: f64 = $tol;
+                const COUNT: usize = 200_000;
+
+                #[test]
+                fn test_sin_cos() {
+                    assert!(max_error(-8.0 * std::f64::consts::PI, 8.0 * std::f64::consts::PI, COUNT, sin, f64::sin) <= TOL);
+                    assert!(max_error(-8.0 * std::f64::consts::PI, 8.0 * std::f64::consts::PI, COUNT, cos, f64::cos) <= TOL);
+                }
+
+                #[test]
+                fn test_sin_cos_large() {
+                    // the edge of the documented range, where the reduction's rounding error is largest
+                    for (lo, hi) in [(9e8, 1e9), (-1e9, -9e8), (9e5, 1e6)] {
+                        assert!(max_error(lo, hi, COUNT, sin, f64::sin) <= TOL, "{lo}..{hi}");
+                        assert!(max_error(lo, hi, COUNT, cos, f64::cos) <= TOL, "{lo}..{hi}");
+                    }
+                    assert!((sin(1e6) as f64 - 1e6_f64.sin()).abs() <= TOL);
+                }
+
+                #[test]
+                fn test_tan() {
+                    // away from the poles, where the error relative to tan(x) blows up
+                    let err = (0..=COUNT)
+                        .map(|i| (-10.0 + 20.0 * i as f64 / COUNT as f64) as f32)
+                        .filter(|&x| (x as f64).cos().abs() > 0.25)
+                        .map(|x| {
+                            let expected = (x as f64).tan();
+                            (tan(x) as f64 - expected).abs() / expected.abs().max(1.0)
+                        })
+                        .fold(0.0, f64::max);
+                    assert!(err <= TOL, "{err}");
+                }
+
+                #[test]
+                fn test_atan2() {
+                    let mut err: f64 = 0.0;
+                    for i in 0..=COUNT {
+                        let theta = -std::f64::consts::PI + std::f64::consts::TAU * i as f64 / COUNT as f64;
+                        for r in [1e-3, 1.0, 7.5, 1e4] {
+                            let (y, x) = ((r * theta.sin()) as f32, (r * theta.cos()) as f32);
+                            err = err.max((atan2(y, x) as f64 - (y as f64).atan2(x as f64)).abs());
+                        }
+                    }
+                    assert!(err <= TOL, "{err}");
+                    assert_eq!(atan2(0.0, -1.0), std::f32::consts::PI);
+                    assert_eq!(atan2(0.0, 0.0), 0.0);
+                }
+
+                #[test]
+                fn test_exp() {
+                    assert!(max_error(-87.0, 88.0, COUNT, exp, f64::exp) <= TOL);
+                    assert!(max_error(-125.0, 127.0, COUNT, exp2, f64::exp2) <= TOL);
+                    assert_eq!(exp(100.0), f32::INFINITY);
+                    assert_eq!(exp(-100.0), 0.0);
+                }
+
+                #[test]
+                fn test_log() {
+                    assert!(max_error(1e-6, 4.0, COUNT, log, f64::ln) <= TOL);
+                    assert!(max_error(1e-6, 4.0, COUNT, log2, f64::log2) <= TOL);
+                    assert!(max_error(4.0, 1e30, COUNT, log, f64::ln) <= TOL);
+                    assert!(max_error(4.0, 1e30, COUNT, log2, f64::log2) <= TOL);
+                    assert!(log(-1.0).is_nan());
+                    assert_eq!(log(0.0), f32::NEG_INFINITY);
+                }
+
+                #[test]
+                fn test_pow() {
+                    for y in [-3.0, -0.5, 0.25, 1.0, 2.5] {
+                        let err = max_error(0.05, 20.0, COUNT / 10, |x| pow(x, y as f32), |x| x.powf(y));
+                        assert!(err <= TOL * (1.0 + (y * 20.0_f64.log2()).abs()), "{y}: {err}");
+                    }
+                }
+
+                #[test]
+                fn test_rcp() {
+                    assert!(max_error(1e-3, 1e3, COUNT, |x| rcp(x) * x, |_| 1.0) <= TOL);
+                    assert!(max_error(-1e3, -1e-3, COUNT, |x| rcp(x) * x, |_| 1.0) <= TOL);
+                    assert_eq!(rcp(0.0), f32::INFINITY);
+                }
+
+                #[test]
+                fn test_vec() {
+                    let v = Vector::<f32, 3>::new(0.5, 1.0, 2.0);
+                    assert_eq!(sin_vec(v), Vector::<f32, 3>::new(sin(0.5), sin(1.0), sin(2.0)));
+                    assert_eq!(pow_vec(v, v), Vector::<f32, 3>::new(pow(0.5, 0.5), pow(1.0, 1.0), pow(2.0, 2.0)));
+                }
+            }
+        };
+    }
+
+    test_tier!(fast, 1e-3);
+    test_tier!(precise, 1e-6);
+}
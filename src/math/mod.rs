@@ -0,0 +1,102 @@
+pub mod approx;
+
+use crate::num::Num;
+
+/// Quake algorithm
+pub fn q_rsqrt(number: f32) -> f32 {
+    q_rsqrt_n::<1>(number)
+}
+
+/// Quake algorithm with `ITERATIONS` Newton steps.
+/// One step is within 0.2% of `1 / sqrt(number)`, two are within 5e-6.
+/// Negative inputs give a meaningless result, but don't panic.
+#[allow(non_upper_case_globals)]
+pub fn q_rsqrt_n<const ITERATIONS: u32>(number: f32) -> f32 {
+    let mut i: u32;
+    let (x2, mut y): (f32, f32);
+    const threehalfs: f32 = 1.5;
+
+    x2 = number * 0.5;
+    y  = number;
+    i  = y.to_bits();                            // evil floating point bit level hacking
+    i  = 0x5f3759df_u32.wrapping_sub( i >> 1 );  // what the fuck?
+    y  = f32::from_bits(i);
+    for _ in 0..ITERATIONS {
+        y  = y * ( threehalfs - ( x2 * y * y )); // Newton iteration
+    }
+
+    y
+}
+
+/// Quake algorithm for `f64`
+pub fn q_rsqrt64(number: f64) -> f64 {
+    q_rsqrt64_n::<1>(number)
+}
+
+/// Quake algorithm for `f64` with `ITERATIONS` Newton steps, using the 64-bit magic constant
+#[allow(non_upper_case_globals)]
+pub fn q_rsqrt64_n<const ITERATIONS: u32>(number: f64) -> f64 {
+    let mut i: u64;
+    let (x2, mut y): (f64, f64);
+    const threehalfs: f64 = 1.5;
+
+    x2 = number * 0.5;
+    y  = number;
+    i  = y.to_bits();
+    i  = 0x5fe6eb50c7b537a9_u64.wrapping_sub( i >> 1 );
+    y  = f64::from_bits(i);
+    for _ in 0..ITERATIONS {
+        y  = y * ( threehalfs - ( x2 * y * y ));
+    }
+
+    y
+}
+
+/// Fast approximate `1 / sqrt(self)` with a single Newton step, for positive, finite, normal values
+pub trait QRsqrt {
+    fn q_rsqrt(self) -> Self;
+
+    /// Backs [`QNormalize`](crate::vec::QNormalize), so `f32` can use SIMD
+    #[doc(hidden)]
+    #[inline]
+    fn qnorm_array<const N: usize>(a: [Self; N]) -> [Self; N] where Self: Num {
+        let scale = Self::dot_array(a, a).q_rsqrt();
+        a.map(#[inline] |comp| comp * scale)
+    }
+}
+
+impl QRsqrt for f32 {
+    fn q_rsqrt(self) -> Self { q_rsqrt(self) }
+
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    #[inline]
+    fn qnorm_array<const N: usize>(a: [Self; N]) -> [Self; N] { crate::simd::qnorm(a) }
+}
+impl QRsqrt for f64 { fn q_rsqrt(self) -> Self { q_rsqrt64(self) } }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_q_rsqrt() {
+        for x in (1..10_000).map(|i| i as f32 * 0.37) {
+            let exact = 1.0 / x.sqrt();
+            assert!((q_rsqrt(x) - exact).abs() / exact < 2e-3);
+            assert!((q_rsqrt_n::<2>(x) - exact).abs() / exact < 5e-6);
+
+            let (x, exact) = (x as f64, 1.0 / (x as f64).sqrt());
+            assert!((q_rsqrt64(x) - exact).abs() / exact < 2e-3);
+            assert!((q_rsqrt64_n::<2>(x) - exact).abs() / exact < 5e-6);
+            assert!((q_rsqrt64_n::<4>(x) - exact).abs() / exact < 1e-14);
+        }
+    }
+    #[test]
+    fn test_q_rsqrt_sign_bit() {
+        // the shifted bits exceed the magic constant once the sign bit is set
+        for x in [-1.0, -0.0, -f32::INFINITY, -f32::NAN, f32::from_bits(u32::MAX)] {
+            let _ = (q_rsqrt(x), q_rsqrt_n::<2>(x), q_rsqrt64(x as f64), q_rsqrt64_n::<2>(x as f64));
+        }
+        let _ = crate::vec::QNormalize::qnorm(crate::vec::Vec2::new(-f32::NAN, 0.0));
+    }
+}
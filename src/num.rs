@@ -0,0 +1,606 @@
+//! Numeric traits implemented by the primitive scalar types
+//!
+//! Generic code should usually only need to name one of [`Num`], [`Signed`], [`Integer`], [`Real`] or [`Float`].
+
+use std::{num::{Saturating, Wrapping}, ops::*};
+
+pub trait Recip {
+    fn recip(self) -> Self;
+}
+
+impl Recip for f32 { fn recip(self) -> Self { self.recip() } }
+impl Recip for f64 { fn recip(self) -> Self { self.recip() } }
+
+/// Division of many numbers by the same denominator, doing the expensive part once
+pub trait ParallelDiv: Sized + Div<Output = Self> {
+    /// The prepared denominator
+    type Divisor: Copy;
+
+    fn denom_or_recip(self) -> Self::Divisor;
+    fn parallel_div(self, denom_or_recip: Self::Divisor) -> Self;
+}
+
+impl<T: Recip + Copy + Div<Output = Self> + Mul<Output = Self>> ParallelDiv for T {
+    type Divisor = Self;
+
+    fn denom_or_recip(self) -> Self::Divisor {
+        self.recip()
+    }
+
+    fn parallel_div(self, recip: Self::Divisor) -> Self {
+        self * recip
+    }
+}
+
+/// An integer denominator turned into a multiply-high and shift, as in libdivide.
+/// Gives the same results as `/`: it panics on zero when created, and on `MIN / -1` when applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntDivisor<T> {
+    /// Zero for powers of two, which only shift
+    magic: T,
+    shift: u32,
+    /// The magic number needed one bit more than `T` has; the numerator is added back in
+    add: bool,
+    negative: bool,
+}
+
+/// High half of the full product, for applying an [`IntDivisor`]
+trait MulHi {
+    fn mulhi(self, rhs: Self) -> Self;
+}
+
+/// Quotient and remainder of `2^(BITS + l) / d` for `2^l < d`, for building an [`IntDivisor`]
+trait Pow2Div: Sized {
+    fn pow2_div(l: u32, d: Self) -> (Self, Self);
+}
+
+macro_rules! impl_magic_div {
+    (signed $($T:ident $Wide:ident),+) => {
+        $(impl MulHi for $T {
+            #[inline]
+            fn mulhi(self, rhs: Self) -> Self { ((self as $Wide * rhs as $Wide) >> <$T>::BITS) as $T }
+        })+
+    };
+    (unsigned $($T:ident $Wide:ident),+) => {
+        impl_magic_div!(signed $($T $Wide),+);
+        $(impl Pow2Div for $T {
+            fn pow2_div(l: u32, d: Self) -> (Self, Self) {
+                let n = (1 as $Wide) << (<$T>::BITS + l);
+                ((n / d as $Wide) as $T, (n % d as $Wide) as $T)
+            }
+        })+
+    };
+}
+
+impl_magic_div!(unsigned u8 u16, u16 u32, u32 u64, u64 u128, usize u128);
+impl_magic_div!(signed i8 i16, i16 i32, i32 i64, i64 i128, isize i128);
+
+impl MulHi for u128 {
+    #[inline]
+    fn mulhi(self, rhs: Self) -> Self {
+        let (a0, a1, b0, b1) = (self as u64 as u128, self >> 64, rhs as u64 as u128, rhs >> 64);
+        let (p01, p10) = (a0 * b1, a1 * b0);
+        let mid = ((a0 * b0) >> 64) + (p01 as u64 as u128) + (p10 as u64 as u128);
+        a1 * b1 + (p01 >> 64) + (p10 >> 64) + (mid >> 64)
+    }
+}
+
+impl Pow2Div for u128 {
+    fn pow2_div(l: u32, d: Self) -> (Self, Self) {
+        // long division of 2^l followed by 128 zero bits; the remainder stays below `d`
+        let (mut q, mut r) = (0, 1u128 << l);
+        for _ in 0..128 {
+            let carry = r >> 127;
+            (q, r) = (q << 1, r << 1);
+            if carry != 0 || r >= d { (q, r) = (q | 1, r.wrapping_sub(d)); }
+        }
+        (q, r)
+    }
+}
+
+impl MulHi for i128 {
+    #[inline]
+    fn mulhi(self, rhs: Self) -> Self {
+        let hi = (self as u128).mulhi(rhs as u128) as i128;
+        hi.wrapping_sub(if self < 0 { rhs } else { 0 }).wrapping_sub(if rhs < 0 { self } else { 0 })
+    }
+}
+
+macro_rules! impl_parallel_div_int {
+    (unsigned $($T:ident),+) => {
+        $(impl ParallelDiv for $T {
+            type Divisor = IntDivisor<$T>;
+
+            fn denom_or_recip(self) -> Self::Divisor {
+                assert!(self != 0, "attempt to divide by zero");
+                let l = <$T>::BITS - 1 - self.leading_zeros();
+                if self.is_power_of_two() {
+                    return IntDivisor { magic: 0, shift: l, add: false, negative: false };
+                }
+                let (m, rem) = <$T>::pow2_div(l, self);
+                if self - rem < 1 << l {
+                    IntDivisor { magic: m + 1, shift: l, add: false, negative: false }
+                } else {
+                    let twice_rem = rem.wrapping_add(rem);
+                    let m = m.wrapping_add(m) + (twice_rem >= self || twice_rem < rem) as $T;
+                    IntDivisor { magic: m.wrapping_add(1), shift: l, add: true, negative: false }
+                }
+            }
+
+            #[inline]
+            fn parallel_div(self, d: Self::Divisor) -> Self {
+                if d.magic == 0 { return self >> d.shift; }
+                let q = d.magic.mulhi(self);
+                if d.add { (((self - q) >> 1) + q) >> d.shift } else { q >> d.shift }
+            }
+        })+
+    };
+
+    (signed $($T:ident $U:ident),+) => {
+        $(impl ParallelDiv for $T {
+            type Divisor = IntDivisor<$T>;
+
+            fn denom_or_recip(self) -> Self::Divisor {
+                assert!(self != 0, "attempt to divide by zero");
+                let (abs, negative) = (self.unsigned_abs(), self < 0);
+                let l = <$U>::BITS - 1 - abs.leading_zeros();
+                if abs.is_power_of_two() {
+                    return IntDivisor { magic: 0, shift: l, add: false, negative };
+                }
+                let (m, rem) = <$U>::pow2_div(l - 1, abs);
+                let (m, shift, add) = if abs - rem < 1 << l {
+                    (m, l - 1, false)
+                } else {
+                    let twice_rem = rem.wrapping_add(rem);
+                    (m.wrapping_add(m) + (twice_rem >= abs || twice_rem < rem) as $U, l, true)
+                };
+                let magic = m.wrapping_add(1) as $T;
+                IntDivisor { magic: if negative { magic.wrapping_neg() } else { magic }, shift, add, negative }
+            }
+
+            #[inline]
+            fn parallel_div(self, d: Self::Divisor) -> Self {
+                if d.magic == 0 {
+                    // round towards zero by biasing negative numerators
+                    let mask = ((1 as $U) << d.shift).wrapping_sub(1) as $T;
+                    let q = self.wrapping_add((self >> (<$T>::BITS - 1)) & mask) >> d.shift;
+                    if !d.negative { return q; }
+                    // only `MIN / -1` gets here with `q == MIN`
+                    return q.checked_neg().expect("attempt to divide with overflow");
+                }
+                let mut q = d.magic.mulhi(self);
+                if d.add { q = q.wrapping_add(if d.negative { self.wrapping_neg() } else { self }); }
+                q >>= d.shift;
+                q - (q >> (<$T>::BITS - 1))
+            }
+        })+
+    };
+}
+
+impl_parallel_div_int!(unsigned u8, u16, u32, u64, u128, usize);
+impl_parallel_div_int!(signed i8 u8, i16 u16, i32 u32, i64 u64, i128 u128, isize usize);
+
+pub trait Sqrt {
+    fn sqrt(self) -> Self;
+}
+
+impl Sqrt for f32 { fn sqrt(self) -> Self { self.sqrt() } }
+impl Sqrt for f64 { fn sqrt(self) -> Self { self.sqrt() } }
+
+/// Additive identity
+pub trait Zero: Sized {
+    fn zero() -> Self;
+    fn is_zero(&self) -> bool;
+}
+
+/// Multiplicative identity
+pub trait One: Sized {
+    fn one() -> Self;
+}
+
+/// Any number closed under the arithmetic operators
+pub trait Num:
+    Copy + PartialEq + PartialOrd + Zero + One + ParallelDiv
+    + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self> + Rem<Output = Self>
+    + AddAssign + SubAssign + MulAssign + DivAssign + RemAssign
+{
+    /// Backs [`DotProduct`](crate::vec::DotProduct), so `f32` can use SIMD
+    #[doc(hidden)]
+    #[inline]
+    fn dot_array<const N: usize>(a: [Self; N], b: [Self; N]) -> Self {
+        a.into_iter().zip(b).fold(Self::zero(), #[inline] |acc, (a, b)| acc + a * b)
+    }
+}
+
+/// A number that can be negative
+pub trait Signed: Num + Neg<Output = Self> {
+    fn abs(self) -> Self;
+    /// `-1`, `0` or `1` for integers; `-1.0` or `1.0` for floats
+    fn signum(self) -> Self;
+    fn is_negative(self) -> bool;
+}
+
+/// A primitive integer
+pub trait Integer:
+    Num + Eq + Ord + Not<Output = Self>
+    + BitAnd<Output = Self> + BitOr<Output = Self> + BitXor<Output = Self>
+    + Shl<u32, Output = Self> + Shr<u32, Output = Self>
+{
+    const MIN: Self;
+    const MAX: Self;
+    const BITS: u32;
+
+    /// `None` on overflow or division by zero
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_div(self, rhs: Self) -> Option<Self>;
+    /// Two's complement wrap-around on overflow
+    fn wrapping_add(self, rhs: Self) -> Self;
+    fn wrapping_sub(self, rhs: Self) -> Self;
+    fn wrapping_mul(self, rhs: Self) -> Self;
+    fn wrapping_div(self, rhs: Self) -> Self;
+    /// Clamps to [`MIN`](Self::MIN) or [`MAX`](Self::MAX) on overflow
+    fn saturating_add(self, rhs: Self) -> Self;
+    fn saturating_sub(self, rhs: Self) -> Self;
+    fn saturating_mul(self, rhs: Self) -> Self;
+    fn saturating_div(self, rhs: Self) -> Self;
+    /// The wrapped result, and whether it overflowed
+    fn overflowing_add(self, rhs: Self) -> (Self, bool);
+    fn overflowing_sub(self, rhs: Self) -> (Self, bool);
+    fn overflowing_mul(self, rhs: Self) -> (Self, bool);
+    fn overflowing_div(self, rhs: Self) -> (Self, bool);
+}
+
+/// An approximation of the real numbers, supporting roots and trigonometry
+pub trait Real: Signed + Sqrt + Recip {
+    const PI: Self;
+    const TAU: Self;
+    const FRAC_PI_2: Self;
+    /// Difference between `1` and the next representable value
+    const EPSILON: Self;
+
+    fn floor(self) -> Self;
+    fn ceil(self) -> Self;
+    fn round(self) -> Self;
+    fn trunc(self) -> Self;
+    fn fract(self) -> Self;
+    /// `self * a + b`
+    fn mul_add(self, a: Self, b: Self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn tan(self) -> Self;
+    fn sin_cos(self) -> (Self, Self);
+    fn asin(self) -> Self;
+    fn acos(self) -> Self;
+    fn atan(self) -> Self;
+    fn atan2(self, other: Self) -> Self;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn powf(self, n: Self) -> Self;
+
+    /// Backs [`Normalize`](crate::vec::Normalize), so `f32` can use SIMD
+    #[doc(hidden)]
+    #[inline]
+    fn norm_array<const N: usize>(a: [Self; N]) -> [Self; N] {
+        let recip = Self::dot_array(a, a).sqrt().denom_or_recip();
+        a.map(#[inline] |comp| comp.parallel_div(recip))
+    }
+}
+
+/// A primitive IEEE 754 floating point number
+pub trait Float: Real {
+    const NAN: Self;
+    const INFINITY: Self;
+    const NEG_INFINITY: Self;
+
+    fn is_nan(self) -> bool;
+    fn is_finite(self) -> bool;
+    fn is_infinite(self) -> bool;
+    fn copysign(self, sign: Self) -> Self;
+    fn exp2(self) -> Self;
+    fn log2(self) -> Self;
+    fn hypot(self, other: Self) -> Self;
+}
+
+macro_rules! impl_num {
+    ($($T:ident),+) => {
+        $(
+        impl Zero for $T { fn zero() -> Self { 0 as $T } fn is_zero(&self) -> bool { *self == 0 as $T } }
+        impl One for $T { fn one() -> Self { 1 as $T } }
+        impl Num for $T { impl_num!(@simd $T); }
+        )+
+    };
+
+    (@simd f32) => {
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        #[inline]
+        fn dot_array<const N: usize>(a: [Self; N], b: [Self; N]) -> Self { crate::simd::dot(a, b) }
+    };
+    (@simd $T:ident) => {};
+}
+
+macro_rules! impl_signed_int {
+    ($($T:ty),+) => {
+        $(impl Signed for $T {
+            fn abs(self) -> Self { self.abs() }
+            fn signum(self) -> Self { self.signum() }
+            fn is_negative(self) -> bool { self.is_negative() }
+        })+
+    };
+}
+
+macro_rules! impl_integer {
+    ($($T:ty),+) => {
+        $(impl Integer for $T {
+            const MIN: Self = <$T>::MIN;
+            const MAX: Self = <$T>::MAX;
+            const BITS: u32 = <$T>::BITS;
+
+            fn checked_add(self, rhs: Self) -> Option<Self> { self.checked_add(rhs) }
+            fn checked_sub(self, rhs: Self) -> Option<Self> { self.checked_sub(rhs) }
+            fn checked_mul(self, rhs: Self) -> Option<Self> { self.checked_mul(rhs) }
+            fn checked_div(self, rhs: Self) -> Option<Self> { self.checked_div(rhs) }
+            fn wrapping_add(self, rhs: Self) -> Self { self.wrapping_add(rhs) }
+            fn wrapping_sub(self, rhs: Self) -> Self { self.wrapping_sub(rhs) }
+            fn wrapping_mul(self, rhs: Self) -> Self { self.wrapping_mul(rhs) }
+            fn wrapping_div(self, rhs: Self) -> Self { self.wrapping_div(rhs) }
+            fn saturating_add(self, rhs: Self) -> Self { self.saturating_add(rhs) }
+            fn saturating_sub(self, rhs: Self) -> Self { self.saturating_sub(rhs) }
+            fn saturating_mul(self, rhs: Self) -> Self { self.saturating_mul(rhs) }
+            fn saturating_div(self, rhs: Self) -> Self { self.saturating_div(rhs) }
+            fn overflowing_add(self, rhs: Self) -> (Self, bool) { self.overflowing_add(rhs) }
+            fn overflowing_sub(self, rhs: Self) -> (Self, bool) { self.overflowing_sub(rhs) }
+            fn overflowing_mul(self, rhs: Self) -> (Self, bool) { self.overflowing_mul(rhs) }
+            fn overflowing_div(self, rhs: Self) -> (Self, bool) { self.overflowing_div(rhs) }
+        })+
+    };
+}
+
+macro_rules! impl_float {
+    ($($T:ident),+) => {
+        $(
+        impl Signed for $T {
+            fn abs(self) -> Self { self.abs() }
+            fn signum(self) -> Self { self.signum() }
+            fn is_negative(self) -> bool { self.is_sign_negative() }
+        }
+
+        impl Real for $T {
+            const PI: Self = std::$T::consts::PI;
+            const TAU: Self = std::$T::consts::TAU;
+            const FRAC_PI_2: Self = std::$T::consts::FRAC_PI_2;
+            const EPSILON: Self = <$T>::EPSILON;
+
+            fn floor(self) -> Self { self.floor() }
+            fn ceil(self) -> Self { self.ceil() }
+            fn round(self) -> Self { self.round() }
+            fn trunc(self) -> Self { self.trunc() }
+            fn fract(self) -> Self { self.fract() }
+            fn mul_add(self, a: Self, b: Self) -> Self { self.mul_add(a, b) }
+            fn sin(self) -> Self { self.sin() }
+            fn cos(self) -> Self { self.cos() }
+            fn tan(self) -> Self { self.tan() }
+            fn sin_cos(self) -> (Self, Self) { self.sin_cos() }
+            fn asin(self) -> Self { self.asin() }
+            fn acos(self) -> Self { self.acos() }
+            fn atan(self) -> Self { self.atan() }
+            fn atan2(self, other: Self) -> Self { self.atan2(other) }
+            fn exp(self) -> Self { self.exp() }
+            fn ln(self) -> Self { self.ln() }
+            fn powf(self, n: Self) -> Self { self.powf(n) }
+
+            impl_float!(@simd $T);
+        }
+
+        impl Float for $T {
+            const NAN: Self = <$T>::NAN;
+            const INFINITY: Self = <$T>::INFINITY;
+            const NEG_INFINITY: Self = <$T>::NEG_INFINITY;
+
+            fn is_nan(self) -> bool { self.is_nan() }
+            fn is_finite(self) -> bool { self.is_finite() }
+            fn is_infinite(self) -> bool { self.is_infinite() }
+            fn copysign(self, sign: Self) -> Self { self.copysign(sign) }
+            fn exp2(self) -> Self { self.exp2() }
+            fn log2(self) -> Self { self.log2() }
+            fn hypot(self, other: Self) -> Self { self.hypot(other) }
+        }
+        )+
+    };
+
+    (@simd f32) => {
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        #[inline]
+        fn norm_array<const N: usize>(a: [Self; N]) -> [Self; N] { crate::simd::norm(a) }
+    };
+    (@simd $T:ident) => {};
+}
+
+impl_num!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+impl_signed_int!(i8, i16, i32, i64, i128, isize);
+impl_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_float!(f32, f64);
+
+/// Lets [`Wrapping`] and [`Saturating`] integers be vector components, with their overflow behavior
+macro_rules! impl_num_overflow {
+    ($($W:ident),+) => {
+        $(
+        impl<T: Integer> Zero for $W<T> { fn zero() -> Self { $W(T::zero()) } fn is_zero(&self) -> bool { self.0.is_zero() } }
+        impl<T: Integer> One for $W<T> { fn one() -> Self { $W(T::one()) } }
+        impl<T: Integer> ParallelDiv for $W<T> where $W<T>: Div<Output = Self> {
+            type Divisor = Self;
+            fn denom_or_recip(self) -> Self::Divisor { self }
+            fn parallel_div(self, denom: Self::Divisor) -> Self { self / denom }
+        }
+        impl<T: Integer> Num for $W<T> where $W<T>:
+            Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self> + Rem<Output = Self>
+            + AddAssign + SubAssign + MulAssign + DivAssign + RemAssign
+        {}
+        )+
+    };
+}
+
+impl_num_overflow!(Wrapping, Saturating);
+
+/// Why a [`TryCast`] failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CastError {
+    /// The value does not fit in the target type
+    OutOfRange,
+    /// NaN has no integer representation
+    NaN,
+}
+
+impl std::fmt::Display for CastError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OutOfRange => f.write_str("value out of range for the target type"),
+            Self::NaN => f.write_str("NaN cannot be converted to the target type"),
+        }
+    }
+}
+
+impl std::error::Error for CastError {}
+
+/// Conversion with the semantics of `as`: truncating, wrapping or saturating as needed
+pub trait Cast<U> {
+    fn cast(self) -> U;
+}
+
+/// Conversion that fails instead of changing the value's magnitude.
+/// Precision may still be lost, e.g. from `i64` to `f32`.
+pub trait TryCast<U> {
+    fn try_cast(self) -> Result<U, CastError>;
+}
+
+macro_rules! impl_cast {
+    ($kind:ident $to:tt $($from:ty),+) => {
+        $(impl_cast!(@$kind $from $to);)+
+    };
+
+    (@int_to_int $from:ty [$($to:ty),+]) => {
+        $(
+        impl Cast<$to> for $from { #[inline] fn cast(self) -> $to { self as $to } }
+        impl TryCast<$to> for $from { #[inline] fn try_cast(self) -> Result<$to, CastError> { <$to>::try_from(self).map_err(|_| CastError::OutOfRange) } }
+        )+
+    };
+    (@int_to_float $from:ty [$($to:ty),+]) => {
+        $(
+        impl Cast<$to> for $from { #[inline] fn cast(self) -> $to { self as $to } }
+        impl TryCast<$to> for $from { #[inline] fn try_cast(self) -> Result<$to, CastError> { Ok(self as $to) } }
+        )+
+    };
+    (@float_to_int $from:ty [$($to:ty),+]) => {
+        $(
+        impl Cast<$to> for $from { #[inline] fn cast(self) -> $to { self as $to } }
+        impl TryCast<$to> for $from {
+            #[inline]
+            fn try_cast(self) -> Result<$to, CastError> {
+                if self.is_nan() { return Err(CastError::NaN); }
+                let t = self.trunc();
+                // `MAX as $from` rounds up to a power of 2 when it isn't exact, so `+ 1.0` gives the exclusive bound either way
+                if t >= <$to>::MIN as $from && t < <$to>::MAX as $from + 1.0 { Ok(t as $to) } else { Err(CastError::OutOfRange) }
+            }
+        }
+        )+
+    };
+    (@float_to_float $from:ty [$($to:ty),+]) => {
+        $(
+        impl Cast<$to> for $from { #[inline] fn cast(self) -> $to { self as $to } }
+        impl TryCast<$to> for $from {
+            #[inline]
+            fn try_cast(self) -> Result<$to, CastError> {
+                let result = self as $to;
+                if self.is_finite() && result.is_infinite() { Err(CastError::OutOfRange) } else { Ok(result) }
+            }
+        }
+        )+
+    };
+    (@bool_to_num $from:ty [$($to:ty),+]) => {
+        $(
+        impl Cast<$to> for $from { #[inline] fn cast(self) -> $to { self as u8 as $to } }
+        impl TryCast<$to> for $from { #[inline] fn try_cast(self) -> Result<$to, CastError> { Ok(self as u8 as $to) } }
+        )+
+    };
+}
+
+impl_cast!(int_to_int     [i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize] i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_cast!(int_to_float   [f32, f64] i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_cast!(float_to_int   [i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize] f32, f64);
+impl_cast!(float_to_float [f32, f64] f32, f64);
+impl_cast!(bool_to_num    [i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64] bool);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every divisor, against every `$debug_stride`th dividend and the extremes in debug builds,
+    /// so the 16-bit types stay fast unoptimized; every dividend with optimizations
+    macro_rules! test_exhaustive {
+        ($($name:ident $T:ident $debug_stride:literal),+) => {
+            $(#[test]
+            fn $name() {
+                let stride = if cfg!(debug_assertions) { $debug_stride } else { 1 };
+                let edges = [<$T>::MIN + 1, <$T>::MAX - 1, <$T>::MAX];
+                for d in <$T>::MIN..=<$T>::MAX {
+                    if d == 0 { continue; }
+                    let divisor = d.denom_or_recip();
+                    for n in (<$T>::MIN..=<$T>::MAX).step_by(stride).chain(edges) {
+                        // `MIN / -1` panics, see `test_parallel_div_overflow`
+                        let Some(q) = n.checked_div(d) else { continue };
+                        assert_eq!(n.parallel_div(divisor), q, "{n} / {d}");
+                    }
+                }
+            })+
+        };
+    }
+
+    test_exhaustive!(
+        test_parallel_div_u8 u8 1, test_parallel_div_i8 i8 1,
+        test_parallel_div_u16_exhaustive u16 61, test_parallel_div_i16_exhaustive i16 61
+    );
+
+    macro_rules! test_sampled {
+        ($($name:ident $T:ident),+) => {
+            $(#[test]
+            fn $name() {
+                let mut state = 0x9e3779b97f4a7c15_u64;
+                let mut next = || {
+                    state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                    let x = (state as u128) << 64 | state.rotate_left(17) as u128;
+                    // vary the magnitude so small divisors show up too
+                    (x >> (state >> 57)) as $T
+                };
+                let edges = [<$T>::MIN, <$T>::MIN + 1, <$T>::MAX, <$T>::MAX - 1, 1, 2, 3, 7, 10, <$T>::MAX / 3];
+                let numerators: Vec<$T> = edges.iter().copied().chain((0..200).map(|_| next())).collect();
+                for d in edges.iter().copied().chain((0..2000).map(|_| next())) {
+                    if d == 0 { continue; }
+                    let divisor = d.denom_or_recip();
+                    for &n in &numerators {
+                        let Some(q) = n.checked_div(d) else { continue };
+                        assert_eq!(n.parallel_div(divisor), q, "{n} / {d}");
+                    }
+                }
+            })+
+        };
+    }
+
+    test_sampled!(
+        test_parallel_div_u16 u16, test_parallel_div_i16 i16,
+        test_parallel_div_u32 u32, test_parallel_div_i32 i32, test_parallel_div_u64 u64, test_parallel_div_i64 i64,
+        test_parallel_div_u128 u128, test_parallel_div_i128 i128, test_parallel_div_usize usize, test_parallel_div_isize isize
+    );
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn test_parallel_div_zero() {
+        let _ = 0_u32.denom_or_recip();
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide with overflow")]
+    fn test_parallel_div_overflow() {
+        let _ = i32::MIN.parallel_div((-1).denom_or_recip());
+    }
+}
@@ -0,0 +1,272 @@
+use std::{fmt, hash::Hash, ops::*};
+use crate::{num::*, vec::*, mat::*, angle::Radians, interp::small, math::QRsqrt};
+
+/// A quaternion `xi + yj + zk + w`, used to represent 3D rotations
+pub struct Quat<T>(pub(crate) Vector<T, 4>);
+
+impl<T: fmt::Debug> fmt::Debug for Quat<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut d = f.debug_tuple("Quat");
+        for comp in &self.0.0 { d.field(comp); }
+        d.finish()
+    }
+}
+
+impl<T: Clone> Clone for Quat<T> { fn clone(&self) -> Self { Self(self.0.clone()) } }
+impl<T: Copy> Copy for Quat<T> {}
+impl<T: PartialEq> PartialEq for Quat<T> { fn eq(&self, other: &Self) -> bool { self.0 == other.0 } }
+impl<T: Eq> Eq for Quat<T> {}
+impl<T: Hash> Hash for Quat<T> { fn hash<H: std::hash::Hasher>(&self, state: &mut H) { self.0.hash(state); } }
+
+impl<T: Copy + Neg<Output = T>> Neg for Quat<T> { type Output = Self; fn neg(self) -> Self::Output { Self(-self.0) } }
+impl<T: Copy + Add<Output = T>> Add for Quat<T> { type Output = Self; fn add(self, rhs: Self) -> Self::Output { Self(self.0 + rhs.0) } }
+impl<T: Copy + Sub<Output = T>> Sub for Quat<T> { type Output = Self; fn sub(self, rhs: Self) -> Self::Output { Self(self.0 - rhs.0) } }
+impl<T: Copy + Mul<Output = T>> Mul<T> for Quat<T> { type Output = Self; fn mul(self, rhs: T) -> Self::Output { Self(self.0 * rhs) } }
+impl<T: Copy + ParallelDiv> Div<T> for Quat<T> { type Output = Self; fn div(self, rhs: T) -> Self::Output { Self(self.0 / rhs) } }
+
+/// Hamilton product; `a * b` rotates by `b` first, then by `a`
+impl<T: Num> Mul for Quat<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        let Vector([x0, y0, z0, w0]) = self.0;
+        let Vector([x1, y1, z1, w1]) = rhs.0;
+        Self::new(
+            w0 * x1 + x0 * w1 + y0 * z1 - z0 * y1,
+            w0 * y1 - x0 * z1 + y0 * w1 + z0 * x1,
+            w0 * z1 + x0 * y1 - y0 * x1 + z0 * w1,
+            w0 * w1 - x0 * x1 - y0 * y1 - z0 * z1,
+        )
+    }
+}
+impl<T: Num> Mul for &Quat<T> { type Output = Quat<T>; fn mul(self, rhs: Self) -> Self::Output { *self * *rhs } }
+
+/// Rotate a vector by a unit quaternion
+impl<T: Num> Mul<Vector<T, 3>> for Quat<T> {
+    type Output = Vector<T, 3>;
+    fn mul(self, rhs: Vector<T, 3>) -> Self::Output {
+        let Vector([x, y, z, w]) = self.0;
+        let u = Vector([x, y, z]);
+        let t = u.cross(rhs);
+        let t = t + t;
+        rhs + t * w + u.cross(t)
+    }
+}
+impl<T: Num> Mul<&Vector<T, 3>> for &Quat<T> { type Output = Vector<T, 3>; fn mul(self, rhs: &Vector<T, 3>) -> Self::Output { *self * *rhs } }
+
+impl<T> From<Vector<T, 4>> for Quat<T> { fn from(value: Vector<T, 4>) -> Self { Self(value) } }
+impl<T> From<Quat<T>> for Vector<T, 4> { fn from(value: Quat<T>) -> Self { value.0 } }
+
+impl<T: Real> Normalize for Quat<T> {
+    type Output = Self;
+    #[inline]
+    fn norm(self) -> Self::Output { Self(self.0.norm()) }
+}
+impl<T: Real> Normalize for &Quat<T> {
+    type Output = Quat<T>;
+    #[inline]
+    fn norm(self) -> Self::Output { Quat((&self.0).norm()) }
+}
+
+impl<T: Num + QRsqrt> QNormalize for Quat<T> {
+    type Output = Self;
+    #[inline]
+    fn qnorm(self) -> Self::Output { Self(self.0.qnorm()) }
+}
+impl<T: Num + QRsqrt> QNormalize for &Quat<T> {
+    type Output = Quat<T>;
+    #[inline]
+    fn qnorm(self) -> Self::Output { Quat((&self.0).qnorm()) }
+}
+
+impl<T> Quat<T> {
+    pub const fn new(x: T, y: T, z: T, w: T) -> Self { Self(Vector([x, y, z, w])) }
+
+    pub const fn x(&self) -> &T { &self.0.0[0] }
+    pub const fn y(&self) -> &T { &self.0.0[1] }
+    pub const fn z(&self) -> &T { &self.0.0[2] }
+    pub const fn w(&self) -> &T { &self.0.0[3] }
+}
+
+impl<T: Zero + One> Quat<T> {
+    /// No rotation
+    pub fn identity() -> Self { Self::new(T::zero(), T::zero(), T::zero(), T::one()) }
+}
+
+impl<T: Copy + Neg<Output = T>> Quat<T> {
+    /// Negate the vector part; the inverse of a unit quaternion
+    #[must_use]
+    pub fn conjugate(self) -> Self {
+        let Vector([x, y, z, w]) = self.0;
+        Self::new(-x, -y, -z, w)
+    }
+}
+
+impl<T: Signed> Quat<T> {
+    /// Rotation undoing `self`, even if `self` is not unit length
+    #[must_use]
+    pub fn inverse(self) -> Self { self.conjugate() / self.0.len_sqr() }
+}
+
+impl<T: Num> DotProduct for Quat<T> {
+    type Output = T;
+    #[inline]
+    fn dot(self, other: Self) -> Self::Output { self.0.dot(other.0) }
+}
+
+/// `x` clamped to `[-1, 1]`, so rounding can't push it outside the domain of `asin`/`acos`
+fn clamp_unit<T: Real>(x: T) -> T {
+    if x > T::one() { T::one() } else if x < -T::one() { -T::one() } else { x }
+}
+
+impl<T: Real> Quat<T> {
+    /// Rotation by `angle` about a unit length `axis`
+    pub fn from_axis_angle(axis: Vector<T, 3>, angle: impl Into<Radians<T>>) -> Self {
+        let (s, c) = (angle.into() / small(2)).sin_cos();
+        let Vector([x, y, z]) = axis * s;
+        Self::new(x, y, z, c)
+    }
+
+    /// Unit length axis and angle
+    pub fn to_axis_angle(self) -> (Vector<T, 3>, Radians<T>) {
+        let Vector([x, y, z, w]) = self.0;
+        let s_sqr = T::one() - w * w;
+        let s = if s_sqr > T::zero() { s_sqr.sqrt() } else { T::zero() };
+        if s <= T::EPSILON {
+            (Vector([T::one(), T::zero(), T::zero()]), Radians(T::zero()))
+        } else {
+            (Vector([x, y, z]) / s, Radians(small::<T>(2) * clamp_unit(w).acos()))
+        }
+    }
+
+    /// Roll about X, then pitch about Y, then yaw about Z
+    pub fn from_euler(roll: impl Into<Radians<T>>, pitch: impl Into<Radians<T>>, yaw: impl Into<Radians<T>>) -> Self {
+        let (sr, cr) = (roll.into()  / small(2)).sin_cos();
+        let (sp, cp) = (pitch.into() / small(2)).sin_cos();
+        let (sy, cy) = (yaw.into()   / small(2)).sin_cos();
+        Self::new(
+            sr * cp * cy - cr * sp * sy,
+            cr * sp * cy + sr * cp * sy,
+            cr * cp * sy - sr * sp * cy,
+            cr * cp * cy + sr * sp * sy,
+        )
+    }
+
+    /// `(roll, pitch, yaw)`, inverse of [`Self::from_euler`]
+    pub fn to_euler(self) -> (Radians<T>, Radians<T>, Radians<T>) {
+        let Vector([x, y, z, w]) = self.0;
+        let (one, two) = (T::one(), small::<T>(2));
+        let roll  = (two * (w * x + y * z)).atan2(one - two * (x * x + y * y));
+        let pitch = clamp_unit(two * (w * y - z * x)).asin();
+        let yaw   = (two * (w * z + x * y)).atan2(one - two * (y * y + z * z));
+        (Radians(roll), Radians(pitch), Radians(yaw))
+    }
+
+    /// Normalized linear interpolation, taking the shortest path
+    #[must_use]
+    pub fn nlerp(self, other: Self, t: T) -> Self {
+        let other = if self.dot(other) < T::zero() { -other } else { other };
+        (self * (T::one() - t) + other * t).norm()
+    }
+
+    /// Spherical linear interpolation, taking the shortest path
+    #[must_use]
+    pub fn slerp(self, other: Self, t: T) -> Self {
+        let d = self.dot(other);
+        let (other, d) = if d < T::zero() { (-other, -d) } else { (other, d) };
+        if d > T::one() - (small::<T>(200) * small::<T>(10)).recip() {
+            // nearly parallel (`d > 0.9995`); sin(theta) is too small to divide by
+            return (self * (T::one() - t) + other * t).norm();
+        }
+        let theta = d.acos();
+        let sin_theta = theta.sin();
+        self * (((T::one() - t) * theta).sin() / sin_theta) + other * ((t * theta).sin() / sin_theta)
+    }
+
+    /// Convert a pure rotation matrix
+    pub fn from_mat3(m: &Matrix<T, 3, 3>) -> Self {
+        let [Vector([m00, m01, m02]), Vector([m10, m11, m12]), Vector([m20, m21, m22])] = m.0;
+        let (one, two) = (T::one(), small::<T>(2));
+        let trace = m00 + m11 + m22;
+        // `s` is four times the largest component, which is then `s / 4`
+        if trace > T::zero() {
+            let s = (trace + one).sqrt() * two;
+            Self::new((m21 - m12) / s, (m02 - m20) / s, (m10 - m01) / s, s / small(4))
+        } else if m00 > m11 && m00 > m22 {
+            let s = (one + m00 - m11 - m22).sqrt() * two;
+            Self::new(s / small(4), (m01 + m10) / s, (m02 + m20) / s, (m21 - m12) / s)
+        } else if m11 > m22 {
+            let s = (one + m11 - m00 - m22).sqrt() * two;
+            Self::new((m01 + m10) / s, s / small(4), (m12 + m21) / s, (m02 - m20) / s)
+        } else {
+            let s = (one + m22 - m00 - m11).sqrt() * two;
+            Self::new((m02 + m20) / s, (m12 + m21) / s, s / small(4), (m10 - m01) / s)
+        }
+    }
+
+    /// Rotation matrix of a unit quaternion
+    pub fn to_mat3(self) -> Matrix<T, 3, 3> {
+        let Vector([x, y, z, w]) = self.0;
+        let (one, two) = (T::one(), small::<T>(2));
+        let (xx, yy, zz) = (x * x, y * y, z * z);
+        let (xy, xz, yz) = (x * y, x * z, y * z);
+        let (wx, wy, wz) = (w * x, w * y, w * z);
+        Matrix::<T, 3, 3>::new(
+            one - two * (yy + zz), two * (xy - wz),       two * (xz + wy),
+            two * (xy + wz),       one - two * (xx + zz), two * (yz - wx),
+            two * (xz - wy),       two * (yz + wx),       one - two * (xx + yy),
+        )
+    }
+}
+
+impl<T: Real> From<Quat<T>> for Matrix<T, 3, 3> { fn from(value: Quat<T>) -> Self { value.to_mat3() } }
+impl<T: Real> From<&Matrix<T, 3, 3>> for Quat<T> { fn from(value: &Matrix<T, 3, 3>) -> Self { Self::from_mat3(value) } }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{angle::Degrees, assert_approx_eq};
+
+    #[test]
+    fn test_rotate() {
+        let q = Quat::<f64>::from_axis_angle(Vector::<f64, 3>::new(0.0, 0.0, 1.0), Degrees(90.0));
+        assert_approx_eq!(q * Vector::<f64, 3>::new(1.0, 0.0, 0.0), Vector::<f64, 3>::new(0.0, 1.0, 0.0), epsilon = 1e-9);
+        assert_approx_eq!(q * q.inverse(), Quat::identity(), epsilon = 1e-9);
+        assert_approx_eq!((q * q) * Vector::<f64, 3>::new(1.0, 0.0, 0.0), Vector::<f64, 3>::new(-1.0, 0.0, 0.0), epsilon = 1e-9);
+
+        let axis = Vector::<f64, 3>::new(0.0, 0.6, -0.8);
+        let (a, angle) = Quat::<f64>::from_axis_angle(axis, Degrees(120.0)).to_axis_angle();
+        assert_approx_eq!(a, axis, epsilon = 1e-9);
+        assert_approx_eq!(angle.to_degrees().0, 120.0, epsilon = 1e-9);
+        assert_eq!(Quat::<f64>::identity().to_axis_angle().1, Radians(0.0));
+    }
+
+    #[test]
+    fn test_euler_and_matrix() {
+        let q = Quat::<f64>::from_euler(Radians(0.3), Radians(-0.7), Degrees(60.0));
+        let (roll, pitch, yaw) = q.to_euler();
+        assert_approx_eq!(Vector::<f64, 3>::new(roll.0, pitch.0, yaw.to_degrees().0), Vector::<f64, 3>::new(0.3, -0.7, 60.0), epsilon = 1e-9);
+
+        let v = Vector::<f64, 3>::new(1.0, 2.0, 3.0);
+        assert_approx_eq!(q.to_mat3() * v, q * v, epsilon = 1e-9);
+        assert_approx_eq!(Quat::<f64>::from_mat3(&q.to_mat3()), q, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_slerp() {
+        let a = Quat::<f64>::identity();
+        let b = Quat::<f64>::from_axis_angle(Vector::<f64, 3>::new(0.0, 1.0, 0.0), Degrees(90.0));
+        let half = Quat::<f64>::from_axis_angle(Vector::<f64, 3>::new(0.0, 1.0, 0.0), Degrees(45.0));
+        assert_approx_eq!(a.slerp(b, 0.5), half, epsilon = 1e-9);
+        assert_approx_eq!(a.nlerp(b, 0.5), half, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_fixed() {
+        type F = crate::fixed::Fixed<16>;
+        let f = F::from_f64;
+        let q = Quat::from_axis_angle(Vector::<F, 3>::new(F::zero(), F::zero(), F::one()), Degrees(f(90.0)));
+        assert_approx_eq!(q * Vector::<F, 3>::new(F::one(), F::zero(), F::zero()), Vector::<F, 3>::new(F::zero(), F::one(), F::zero()), epsilon = f(1e-3));
+        assert_approx_eq!(Quat::from_mat3(&q.to_mat3()), q, epsilon = f(1e-3));
+        assert_approx_eq!(Quat::identity().slerp(q * q, f(0.5)), q, epsilon = f(1e-3));
+    }
+}
@@ -0,0 +1,152 @@
+//! SSE kernels behind the `simd` feature, used by the `f32` implementations of [`Num`], [`Real`]
+//! and [`QRsqrt`] for 3- and 4-component vectors
+//!
+//! SSE2 is always available on x86_64; dot products use SSE4.1 `dpps` when the target enables it
+//! (e.g. `-C target-cpu=native`). `Vector<f32, 3>` is padded to four lanes with a zero.
+//!
+//! Differences from the scalar path:
+//! - Dot products add the lanes in a different order than left to right, so the result can differ
+//!   in the last bits, by at most `2 * f32::EPSILON * Σ|aᵢbᵢ|`. Lengths, distances and `norm`
+//!   inherit this; they are otherwise computed exactly like the scalar path.
+//! - `qnorm` uses `rsqrtps` refined by one Newton-Raphson step instead of [`q_rsqrt`], so its
+//!   length is within `1e-6` of one rather than `2e-3`. `rsqrtps` differs between CPU vendors,
+//!   so `qnorm` is not bit-reproducible across machines. A vector whose squared length is zero
+//!   or subnormal, which `rsqrtps` treats as zero, is returned unchanged instead of as NaN; the
+//!   scalar path also returns the zero vector unchanged.
+//!
+//! Component-wise arithmetic needs no kernels: operations on `[f32; 4]` through `array::from_fn`
+//! already compile to packed instructions. `benches/simd.rs` compares them with `_mm_add_ps` and
+//! `_mm_mul_ps`, which are no faster for `Vec4` and slower for `Vec3` because of the padding.
+//!
+//! [`Num`]: crate::num::Num
+//! [`Real`]: crate::num::Real
+//! [`QRsqrt`]: crate::math::QRsqrt
+//! [`q_rsqrt`]: crate::math::q_rsqrt
+
+use std::arch::x86_64::*;
+
+// SAFETY (for every `unsafe` block below): SSE and SSE2 are part of the x86_64 baseline, and
+// SSE4.1 is only used when the target enables it. Loads and stores go through four-float arrays.
+
+#[inline]
+fn load<const N: usize>(a: &[f32; N]) -> __m128 {
+    let mut padded = [0.0; 4];
+    padded[..N].copy_from_slice(a);
+    unsafe { _mm_loadu_ps(padded.as_ptr()) }
+}
+
+#[inline]
+fn store<const N: usize>(v: __m128) -> [f32; N] {
+    let mut out = [0.0; 4];
+    unsafe { _mm_storeu_ps(out.as_mut_ptr(), v) };
+    std::array::from_fn(#[inline] |i| out[i])
+}
+
+/// The dot product, in every lane
+#[inline]
+fn dot_splat(a: __m128, b: __m128) -> __m128 {
+    #[cfg(target_feature = "sse4.1")]
+    unsafe { _mm_dp_ps::<0xff>(a, b) }
+    #[cfg(not(target_feature = "sse4.1"))]
+    unsafe {
+        let p = _mm_mul_ps(a, b);
+        let s = _mm_add_ps(p, _mm_shuffle_ps::<0b10_11_00_01>(p, p));
+        _mm_add_ps(s, _mm_shuffle_ps::<0b01_00_11_10>(s, s))
+    }
+}
+
+#[inline]
+pub(crate) fn dot<const N: usize>(a: [f32; N], b: [f32; N]) -> f32 {
+    if N != 3 && N != 4 {
+        return a.into_iter().zip(b).fold(0.0, #[inline] |acc, (a, b)| acc + a * b);
+    }
+    unsafe { _mm_cvtss_f32(dot_splat(load(&a), load(&b))) }
+}
+
+#[inline]
+pub(crate) fn norm<const N: usize>(a: [f32; N]) -> [f32; N] {
+    if N != 3 && N != 4 {
+        let recip = dot(a, a).sqrt().recip();
+        return a.map(#[inline] |comp| comp * recip);
+    }
+    let v = load(&a);
+    unsafe {
+        let recip = _mm_div_ps(_mm_set1_ps(1.0), _mm_sqrt_ps(dot_splat(v, v)));
+        store(_mm_mul_ps(v, recip))
+    }
+}
+
+#[inline]
+pub(crate) fn qnorm<const N: usize>(a: [f32; N]) -> [f32; N] {
+    if N != 3 && N != 4 {
+        let scale = crate::math::q_rsqrt(dot(a, a));
+        return a.map(#[inline] |comp| comp * scale);
+    }
+    let v = load(&a);
+    let len_sqr = dot_splat(v, v);
+    // `rsqrtps` gives infinity here, and `0 * inf` is NaN
+    if unsafe { _mm_cvtss_f32(len_sqr) } < f32::MIN_POSITIVE { return a; }
+    unsafe {
+        // one Newton-Raphson step: r * (1.5 - 0.5 * len_sqr * r * r)
+        let r = _mm_rsqrt_ps(len_sqr);
+        let half_lr2 = _mm_mul_ps(_mm_mul_ps(_mm_set1_ps(0.5), len_sqr), _mm_mul_ps(r, r));
+        let r = _mm_mul_ps(r, _mm_sub_ps(_mm_set1_ps(1.5), half_lr2));
+        store(_mm_mul_ps(v, r))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{prelude::*, math::q_rsqrt, assert_approx_eq};
+
+    // the default trait methods the kernels replace
+    fn scalar_dot<const N: usize>(a: Vector<f32, N>, b: Vector<f32, N>) -> f32 {
+        a.0.into_iter().zip(b.0).fold(0.0, |acc, (a, b)| acc + a * b)
+    }
+    fn scalar_norm<const N: usize>(a: Vector<f32, N>) -> Vector<f32, N> {
+        let recip = scalar_dot(a, a).sqrt().recip();
+        a.map(|comp| comp * recip)
+    }
+    fn scalar_qnorm<const N: usize>(a: Vector<f32, N>) -> Vector<f32, N> {
+        let scale = q_rsqrt(scalar_dot(a, a));
+        a.map(|comp| comp * scale)
+    }
+
+    fn samples() -> impl Iterator<Item = Vec4> {
+        (1..2000).map(|i| {
+            let t = i as f32 * 0.618;
+            Vec4::new(t.sin() * 30.0, (t * 1.3).cos() * 0.01, t.fract() - 0.5, (t * 0.7).sin() * 1e3)
+        })
+    }
+
+    fn check<const N: usize>(a: Vector<f32, N>, b: Vector<f32, N>) {
+        let bound = 2.0 * f32::EPSILON * (a * b).abs().sum();
+        assert!((a.dot(b) - scalar_dot(a, b)).abs() <= bound, "{a:?} . {b:?}");
+        // `len_sqr` is within `2ε` relative, so its square root within `ε`, plus the rounding of both paths
+        assert_approx_eq!(a.norm(), scalar_norm(a), relative = 3.0 * f32::EPSILON);
+        // the documented tolerances: `rsqrtps` + Newton against one step of `q_rsqrt`
+        assert_approx_eq!(a.qnorm(), scalar_qnorm(a), epsilon = 2e-3);
+        assert!((a.qnorm().len() - 1.0).abs() < 1e-6, "{a:?}");
+    }
+
+    #[test]
+    fn test_against_scalar() {
+        for (a, b) in samples().zip(samples().skip(7)) {
+            check(a, b);
+            check(a.xyz(), b.xyz());
+        }
+        assert_eq!(Vec2::new(3.0, 4.0).norm(), Vec2::new(0.6, 0.8));
+    }
+
+    #[test]
+    fn test_zero() {
+        assert_eq!(Vec4::zero().qnorm(), scalar_qnorm(Vec4::zero()));
+        assert_eq!(Vec3::zero().qnorm(), scalar_qnorm(Vec3::zero()));
+        assert_eq!(Vec3::zero().qnorm(), Vec3::zero());
+        assert_eq!(Vec4::new(0.0, -0.0, 0.0, 0.0).qnorm(), Vec4::new(0.0, -0.0, 0.0, 0.0));
+        assert_eq!(Vec4::zero().dot(Vec4::one()), 0.0);
+        // like the scalar path, normalizing the zero vector divides zero by zero
+        assert!(Vec4::zero().norm().0.iter().all(|c| c.is_nan()));
+        assert!(scalar_norm(Vec4::zero()).0.iter().all(|c| c.is_nan()));
+    }
+}
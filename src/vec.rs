@@ -1,59 +1,79 @@
 use paste::paste;
 use std::{fmt, hash::Hash, ops::*};
-use crate::math::*;
+use crate::{math::*, num::*};
 
-pub trait Recip {
-    fn recip(self) -> Self;
-}
+pub use crate::num::{Recip, ParallelDiv, Sqrt};
 
-impl Recip for f32 { fn recip(self) -> Self { self.recip() } }
-impl Recip for f64 { fn recip(self) -> Self { self.recip() } }
+pub struct Vector<T, const N: usize>(pub(crate) [T; N]);
 
-pub trait ParallelDiv: Sized + Div<Output = Self> {
-    fn denom_or_recip(self) -> Self {
-        self
+impl<T: fmt::Debug, const N: usize> fmt::Debug for Vector<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut d = f.debug_tuple("Vector");
+        for comp in &self.0 { d.field(comp); }
+        d.finish()
     }
+}
 
-    fn parallel_div(self, denom_or_recip: Self) -> Self {
-        self / denom_or_recip
+/// Formats as `(x, y, z)`, passing width and precision on to every component
+impl<T: fmt::Display, const N: usize> fmt::Display for Vector<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("(")?;
+        for (i, comp) in self.0.iter().enumerate() {
+            if i > 0 { f.write_str(", ")?; }
+            comp.fmt(f)?;
+        }
+        f.write_str(")")
     }
 }
 
-impl<T: Recip + Div<Output = Self> + Mul<Output = Self>> ParallelDiv for T {
-    fn denom_or_recip(self) -> Self {
-        self.recip()
-    }
+/// Error from parsing a [`Vector`] with [`FromStr`](std::str::FromStr)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseVectorError<E> {
+    /// The text has a different number of components than the vector
+    WrongArity { expected: usize, found: usize },
+    /// A component failed to parse
+    Component { index: usize, error: E },
+}
 
-    fn parallel_div(self, recip: Self) -> Self {
-        self * recip
+impl<E: fmt::Display> fmt::Display for ParseVectorError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongArity { expected, found } => write!(f, "expected {expected} components, found {found}"),
+            Self::Component { index, error } => write!(f, "invalid component {index}: {error}"),
+        }
     }
 }
 
-impl ParallelDiv for i8   {}
-impl ParallelDiv for i16  {}
-impl ParallelDiv for i32  {}
-impl ParallelDiv for i64  {}
-impl ParallelDiv for i128 {}
-impl ParallelDiv for u8   {}
-impl ParallelDiv for u16  {}
-impl ParallelDiv for u32  {}
-impl ParallelDiv for u64  {}
-impl ParallelDiv for u128 {}
-
-pub trait Sqrt {
-    fn sqrt(self) -> Self;
+impl<E: std::error::Error + 'static> std::error::Error for ParseVectorError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::WrongArity { .. } => None,
+            Self::Component { error, .. } => Some(error),
+        }
+    }
 }
 
-impl Sqrt for f32 { fn sqrt(self) -> Self { self.sqrt() } }
-impl Sqrt for f64 { fn sqrt(self) -> Self { self.sqrt() } }
+/// Parses `(1, 2, 3)`, `[1, 2, 3]`, `1, 2, 3` or `1 2 3`
+impl<T: std::str::FromStr, const N: usize> std::str::FromStr for Vector<T, N> {
+    type Err = ParseVectorError<T::Err>;
 
-pub struct Vector<T, const N: usize>([T; N]);
-
-impl<T: fmt::Debug, const N: usize> fmt::Debug for Vector<T, N> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut d = f.debug_tuple("Vector");
-        for comp in &self.0 { d.field(comp); }
-        d.finish()
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let s = s.strip_prefix('(').and_then(|s| s.strip_suffix(')'))
+            .or_else(|| s.strip_prefix('[').and_then(|s| s.strip_suffix(']')))
+            .unwrap_or(s);
+        let parts: Vec<&str> = if s.contains(',') {
+            s.split(',').map(str::trim).collect()
+        } else {
+            s.split_whitespace().collect()
+        };
+        if parts.len() != N {
+            return Err(ParseVectorError::WrongArity { expected: N, found: parts.len() });
+        }
+        let comps = parts.into_iter().enumerate()
+            .map(#[inline] |(index, part)| part.parse().map_err(|error| ParseVectorError::Component { index, error }))
+            .collect::<Result<Vec<T>, _>>()?;
+        Ok(Self::try_from(comps).unwrap_or_else(|_| unreachable!()))
     }
 }
 
@@ -63,6 +83,10 @@ impl<T: PartialEq, const N: usize> PartialEq for Vector<T, N> { fn eq(&self, oth
 impl<T: Eq, const N: usize> Eq for Vector<T, N> {}
 impl<T: Hash, const N: usize> Hash for Vector<T, N> { fn hash<H: std::hash::Hasher>(&self, state: &mut H) { self.0.hash(state); } }
 
+impl<T: Default, const N: usize> Default for Vector<T, N> { fn default() -> Self { Self(std::array::from_fn(#[inline] |_| T::default())) } }
+impl<T: Zero, const N: usize> Zero for Vector<T, N> { fn zero() -> Self { Self(std::array::from_fn(#[inline] |_| T::zero())) } fn is_zero(&self) -> bool { self.0.iter().all(T::is_zero) } }
+impl<T: One, const N: usize> One for Vector<T, N> { fn one() -> Self { Self(std::array::from_fn(#[inline] |_| T::one())) } }
+
 impl<T: Copy + Neg<Output = T>, const N: usize> Neg for Vector<T, N> { type Output = Self; fn neg(self) -> Self::Output { Self(std::array::from_fn(#[inline] |i| self.0[i].neg())) } }
 impl<T: Copy + Add<Output = T>, const N: usize> Add for Vector<T, N> { type Output = Self; fn add(self, rhs: Self) -> Self::Output { Self(std::array::from_fn(#[inline] |i| self.0[i].add(rhs.0[i]))) } }
 impl<T: Copy + Sub<Output = T>, const N: usize> Sub for Vector<T, N> { type Output = Self; fn sub(self, rhs: Self) -> Self::Output { Self(std::array::from_fn(#[inline] |i| self.0[i].sub(rhs.0[i]))) } }
@@ -85,32 +109,89 @@ impl<T: Copy + Sub<Output = T>, const N: usize> Sub<T> for &Vector<T, N> { type
 impl<T: Copy + Mul<Output = T>, const N: usize> Mul<T> for &Vector<T, N> { type Output = Vector<T, N>; fn mul(self, rhs: T) -> Self::Output { Vector(self.0.map(#[inline] |comp| comp.mul(rhs))) } }
 impl<T: Copy + ParallelDiv, const N: usize> Div<T> for &Vector<T, N> { type Output = Vector<T, N>; fn div(self, rhs: T) -> Self::Output { let denom_or_recip = rhs.denom_or_recip(); Vector(self.0.map(#[inline] |comp| comp.parallel_div(denom_or_recip))) } }
 
+/// Component-wise binary operators with vector and scalar right-hand sides, owned and borrowed
+macro_rules! impl_bin_op {
+    ($($Op:ident $op:ident),+) => {
+        $(
+        impl<T: Copy + $Op<Output = T>, const N: usize> $Op for Vector<T, N> { type Output = Self; fn $op(self, rhs: Self) -> Self::Output { Self(std::array::from_fn(#[inline] |i| self.0[i].$op(rhs.0[i]))) } }
+        impl<T: Copy + $Op<Output = T>, const N: usize> $Op for &Vector<T, N> { type Output = Vector<T, N>; fn $op(self, rhs: Self) -> Self::Output { Vector(std::array::from_fn(#[inline] |i| self.0[i].$op(rhs.0[i]))) } }
+        impl<T: Copy + $Op<Output = T>, const N: usize> $Op<T> for Vector<T, N> { type Output = Self; fn $op(self, rhs: T) -> Self::Output { Self(self.0.map(#[inline] |comp| comp.$op(rhs))) } }
+        impl<T: Copy + $Op<Output = T>, const N: usize> $Op<T> for &Vector<T, N> { type Output = Vector<T, N>; fn $op(self, rhs: T) -> Self::Output { Vector(self.0.map(#[inline] |comp| comp.$op(rhs))) } }
+        )+
+    };
+}
+impl_bin_op!(Rem rem, BitAnd bitand, BitOr bitor, BitXor bitxor, Shl shl, Shr shr);
+
+impl<T: Copy + Not<Output = T>, const N: usize> Not for Vector<T, N> { type Output = Self; fn not(self) -> Self::Output { Self(self.0.map(#[inline] |comp| comp.not())) } }
+impl<T: Copy + Not<Output = T>, const N: usize> Not for &Vector<T, N> { type Output = Vector<T, N>; fn not(self) -> Self::Output { Vector(self.0.map(#[inline] |comp| comp.not())) } }
+
+/// Compound assignment operators with vector and scalar right-hand sides
+macro_rules! impl_assign_op {
+    ($($OpAssign:ident $op_assign:ident),+) => {
+        $(
+        impl<T: Copy + $OpAssign, const N: usize> $OpAssign for Vector<T, N> { fn $op_assign(&mut self, rhs: Self) { for (comp, rhs) in self.0.iter_mut().zip(rhs.0) { comp.$op_assign(rhs); } } }
+        impl<T: Copy + $OpAssign, const N: usize> $OpAssign<&Vector<T, N>> for Vector<T, N> { fn $op_assign(&mut self, rhs: &Self) { for (comp, rhs) in self.0.iter_mut().zip(rhs.0) { comp.$op_assign(rhs); } } }
+        impl<T: Copy + $OpAssign, const N: usize> $OpAssign<T> for Vector<T, N> { fn $op_assign(&mut self, rhs: T) { for comp in &mut self.0 { comp.$op_assign(rhs); } } }
+        )+
+    };
+}
+impl_assign_op!(AddAssign add_assign, SubAssign sub_assign, MulAssign mul_assign, RemAssign rem_assign, BitAndAssign bitand_assign, BitOrAssign bitor_assign, BitXorAssign bitxor_assign, ShlAssign shl_assign, ShrAssign shr_assign);
+
+impl<T: Copy + DivAssign, const N: usize> DivAssign for Vector<T, N> { fn div_assign(&mut self, rhs: Self) { for (comp, rhs) in self.0.iter_mut().zip(rhs.0) { comp.div_assign(rhs); } } }
+impl<T: Copy + DivAssign, const N: usize> DivAssign<&Vector<T, N>> for Vector<T, N> { fn div_assign(&mut self, rhs: &Self) { for (comp, rhs) in self.0.iter_mut().zip(rhs.0) { comp.div_assign(rhs); } } }
+impl<T: Copy + ParallelDiv, const N: usize> DivAssign<T> for Vector<T, N> { fn div_assign(&mut self, rhs: T) { let denom_or_recip = rhs.denom_or_recip(); for comp in &mut self.0 { *comp = comp.parallel_div(denom_or_recip); } } }
+
 impl<T, const N: usize> From<[T; N]> for Vector<T, N> { fn from(value: [T; N]) -> Self { Self(value) } }
 impl<T, const N: usize> From<Vector<T, N>> for [T; N] { fn from(value: Vector<T, N>) -> Self { value.0 } }
 impl<T, const N: usize> TryFrom<Vec<T>> for Vector<T, N> { type Error = <[T; N] as TryFrom<Vec<T>>>::Error; fn try_from(value: Vec<T>) -> Result<Self, Self::Error> { Ok(Self(<[T; N]>::try_from(value)?)) } }
 impl<T, const N: usize> From<Vector<T, N>> for Vec<T> { fn from(value: Vector<T, N>) -> Self { Vec::from(value.0) } }
 impl<T, const N: usize> IntoIterator for Vector<T, N> { type Item = T; type IntoIter = <[T; N] as IntoIterator>::IntoIter; fn into_iter(self) -> Self::IntoIter { self.0.into_iter() } }
+impl<'a, T, const N: usize> IntoIterator for &'a Vector<T, N> { type Item = &'a T; type IntoIter = std::slice::Iter<'a, T>; fn into_iter(self) -> Self::IntoIter { self.0.iter() } }
+impl<'a, T, const N: usize> IntoIterator for &'a mut Vector<T, N> { type Item = &'a mut T; type IntoIter = std::slice::IterMut<'a, T>; fn into_iter(self) -> Self::IntoIter { self.0.iter_mut() } }
+impl<T, const N: usize> Index<usize> for Vector<T, N> { type Output = T; fn index(&self, index: usize) -> &Self::Output { &self.0[index] } }
+impl<T, const N: usize> IndexMut<usize> for Vector<T, N> { fn index_mut(&mut self, index: usize) -> &mut Self::Output { &mut self.0[index] } }
+impl<T, const N: usize> AsRef<[T]> for Vector<T, N> { fn as_ref(&self) -> &[T] { &self.0 } }
+impl<T, const N: usize> AsMut<[T]> for Vector<T, N> { fn as_mut(&mut self) -> &mut [T] { &mut self.0 } }
+impl<T: Num, const N: usize> std::iter::Sum for Vector<T, N> { fn sum<I: Iterator<Item = Self>>(iter: I) -> Self { iter.fold(Self::zero(), Add::add) } }
+impl<'a, T: Num, const N: usize> std::iter::Sum<&'a Vector<T, N>> for Vector<T, N> { fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self { iter.fold(Self::zero(), #[inline] |acc, v| acc + *v) } }
+impl<T: Num, const N: usize> std::iter::Product for Vector<T, N> { fn product<I: Iterator<Item = Self>>(iter: I) -> Self { iter.fold(Self::one(), Mul::mul) } }
+impl<'a, T: Num, const N: usize> std::iter::Product<&'a Vector<T, N>> for Vector<T, N> { fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self { iter.fold(Self::one(), #[inline] |acc, v| acc * *v) } }
 // impl<T, const N: usize> Deref for Vector<T, N> { type Target = [T; N]; fn deref(&self) -> &Self::Target { &self.0 } }
 // impl<T, const N: usize> DerefMut for Vector<T, N> { fn deref_mut(&mut self) -> &mut Self::Target { &mut self.0 } }
 
+/// Lossless component conversions, mirroring the `From` impls between primitives
+macro_rules! impl_widen {
+    ($($from:ty => $($to:ty),+;)+) => {
+        $($(impl<const N: usize> From<Vector<$from, N>> for Vector<$to, N> { fn from(value: Vector<$from, N>) -> Self { Self(value.0.map(<$to>::from)) } })+)+
+    };
+}
+impl_widen! {
+    i8   => i16, i32, i64, i128, isize, f32, f64;
+    i16  => i32, i64, i128, isize, f32, f64;
+    i32  => i64, i128, f64;
+    i64  => i128;
+    u8   => u16, u32, u64, u128, usize, i16, i32, i64, i128, isize, f32, f64;
+    u16  => u32, u64, u128, usize, i32, i64, i128, f32, f64;
+    u32  => u64, u128, i64, i128, f64;
+    u64  => u128, i128;
+    f32  => f64;
+    bool => i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64;
+}
+
 pub trait DotProduct {
     type Output;
     #[must_use]
     fn dot(self, other: Self) -> Self::Output;
 }
-impl<T, const N: usize> DotProduct for Vector<T, N> where Self: Mul<Output = Self>, T: std::iter::Sum {
+impl<T: Num, const N: usize> DotProduct for Vector<T, N> {
     type Output = T;
     #[inline]
-    fn dot(self, other: Self) -> Self::Output {
-        (self * other).into_iter().sum()
-    }
+    fn dot(self, other: Self) -> Self::Output { T::dot_array(self.0, other.0) }
 }
-impl<T, const N: usize> DotProduct for &Vector<T, N> where Self: Mul<Output = Vector<T, N>>, T: std::iter::Sum {
+impl<T: Num, const N: usize> DotProduct for &Vector<T, N> {
     type Output = T;
     #[inline]
-    fn dot(self, other: Self) -> Self::Output {
-        (self * other).into_iter().sum()
-    }
+    fn dot(self, other: Self) -> Self::Output { (*self).dot(*other) }
 }
 
 pub trait MagnitudeSqr {
@@ -118,13 +199,13 @@ pub trait MagnitudeSqr {
     #[must_use]
     fn len_sqr(self) -> Self::Output;
 }
-impl<T, const N: usize> MagnitudeSqr for Vector<T, N> where Self: Clone + DotProduct {
-    type Output = <Self as DotProduct>::Output;
+impl<T: Num, const N: usize> MagnitudeSqr for Vector<T, N> {
+    type Output = T;
     #[inline]
-    fn len_sqr(self) -> Self::Output { self.clone().dot(self) }
+    fn len_sqr(self) -> Self::Output { self.dot(self) }
 }
-impl<T, const N: usize> MagnitudeSqr for &Vector<T, N> where Self: DotProduct {
-    type Output = <Self as DotProduct>::Output;
+impl<T: Num, const N: usize> MagnitudeSqr for &Vector<T, N> {
+    type Output = T;
     #[inline]
     fn len_sqr(self) -> Self::Output { self.dot(self) }
 }
@@ -134,29 +215,30 @@ pub trait DistanceSqr {
     #[must_use]
     fn dist_sqr(self, other: Self) -> Self::Output;
 }
-impl<T, const N: usize, U: MagnitudeSqr> DistanceSqr for Vector<T, N> where Self: Sub<Output = U> {
-    type Output = U::Output;
+impl<T: Num, const N: usize> DistanceSqr for Vector<T, N> {
+    type Output = T;
     #[inline]
     fn dist_sqr(self, other: Self) -> Self::Output { (other - self).len_sqr() }
 }
-impl<T, const N: usize, U: MagnitudeSqr> DistanceSqr for &Vector<T, N> where Self: Sub<Output = U> {
-    type Output = U::Output;
+impl<T: Num, const N: usize> DistanceSqr for &Vector<T, N> {
+    type Output = T;
     #[inline]
     fn dist_sqr(self, other: Self) -> Self::Output { (other - self).len_sqr() }
 }
 
+#[allow(clippy::len_without_is_empty)]
 pub trait Magnitude {
     type Output;
     #[must_use]
     fn len(self) -> Self::Output;
 }
-impl<T, const N: usize, U: Sqrt> Magnitude for Vector<T, N> where Self: MagnitudeSqr<Output = U> {
-    type Output = U;
+impl<T: Real, const N: usize> Magnitude for Vector<T, N> {
+    type Output = T;
     #[inline]
     fn len(self) -> Self::Output { self.len_sqr().sqrt() }
 }
-impl<T, const N: usize, U: Sqrt> Magnitude for &Vector<T, N> where Self: MagnitudeSqr<Output = U> {
-    type Output = U;
+impl<T: Real, const N: usize> Magnitude for &Vector<T, N> {
+    type Output = T;
     #[inline]
     fn len(self) -> Self::Output { self.len_sqr().sqrt() }
 }
@@ -166,13 +248,13 @@ pub trait Distance {
     #[must_use]
     fn dist(self, other: Self) -> Self::Output;
 }
-impl<T, const N: usize, U: Sqrt> Distance for Vector<T, N> where Self: DistanceSqr<Output = U> {
-    type Output = U;
+impl<T: Real, const N: usize> Distance for Vector<T, N> {
+    type Output = T;
     #[inline]
     fn dist(self, other: Self) -> Self::Output { self.dist_sqr(other).sqrt() }
 }
-impl<T, const N: usize, U: Sqrt> Distance for &Vector<T, N> where Self: DistanceSqr<Output = U> {
-    type Output = U;
+impl<T: Real, const N: usize> Distance for &Vector<T, N> {
+    type Output = T;
     #[inline]
     fn dist(self, other: Self) -> Self::Output { self.dist_sqr(other).sqrt() }
 }
@@ -182,31 +264,32 @@ pub trait Normalize {
     #[must_use]
     fn norm(self) -> Self::Output;
 }
-impl<T, const N: usize> Normalize for Vector<T, N> where Self: Clone + Magnitude + Div<<Self as Magnitude>::Output> {
-    type Output = <Self as Div<<Self as Magnitude>::Output>>::Output;
+impl<T: Real, const N: usize> Normalize for Vector<T, N> {
+    type Output = Self;
     #[inline]
-    fn norm(self) -> Self::Output { self.clone() / self.len() }
+    fn norm(self) -> Self::Output { Vector(T::norm_array(self.0)) }
 }
-impl<T, const N: usize> Normalize for &Vector<T, N> where Self: Magnitude + Div<<Self as Magnitude>::Output> {
-    type Output = <Self as Div<<Self as Magnitude>::Output>>::Output;
+impl<T: Real, const N: usize> Normalize for &Vector<T, N> {
+    type Output = Vector<T, N>;
     #[inline]
-    fn norm(self) -> Self::Output { self / self.len() }
+    fn norm(self) -> Self::Output { Vector(T::norm_array(self.0)) }
 }
 
+/// Normalize with [`q_rsqrt`] instead of a square root and division
 pub trait QNormalize {
     type Output;
     #[must_use]
-    unsafe fn qnorm(self) -> Self::Output;
+    fn qnorm(self) -> Self::Output;
 }
-impl<const N: usize> QNormalize for Vector<f32, N> {
+impl<T: Num + QRsqrt, const N: usize> QNormalize for Vector<T, N> {
     type Output = Self;
     #[inline]
-    unsafe fn qnorm(self) -> Self::Output { self.clone() * q_rsqrt(self.len_sqr()) }
+    fn qnorm(self) -> Self::Output { Vector(T::qnorm_array(self.0)) }
 }
-impl<const N: usize> QNormalize for &Vector<f32, N> {
-    type Output = Vector<f32, N>;
+impl<T: Num + QRsqrt, const N: usize> QNormalize for &Vector<T, N> {
+    type Output = Vector<T, N>;
     #[inline]
-    unsafe fn qnorm(self) -> Self::Output { self * q_rsqrt(self.len_sqr()) }
+    fn qnorm(self) -> Self::Output { Vector(T::qnorm_array(self.0)) }
 }
 
 pub trait Direction {
@@ -214,13 +297,13 @@ pub trait Direction {
     #[must_use]
     fn dir(self, other: Self) -> Self::Output;
 }
-impl<T, const N: usize, U: Normalize> Direction for Vector<T, N> where Self: Clone + Sub<Output = U> {
-    type Output = U::Output;
+impl<T: Real, const N: usize> Direction for Vector<T, N> {
+    type Output = Self;
     #[inline]
     fn dir(self, other: Self) -> Self::Output { (other - self).norm() }
 }
-impl<T, const N: usize, U: Normalize> Direction for &Vector<T, N> where Self: Sub<Output = U> {
-    type Output = U::Output;
+impl<T: Real, const N: usize> Direction for &Vector<T, N> {
+    type Output = Vector<T, N>;
     #[inline]
     fn dir(self, other: Self) -> Self::Output { (other - self).norm() }
 }
@@ -228,23 +311,262 @@ impl<T, const N: usize, U: Normalize> Direction for &Vector<T, N> where Self: Su
 pub trait QDirection {
     type Output;
     #[must_use]
-    unsafe fn qdir(self, other: Self) -> Self::Output;
+    fn qdir(self, other: Self) -> Self::Output;
+}
+impl<T: Num + QRsqrt, const N: usize> QDirection for Vector<T, N> {
+    type Output = Self;
+    #[inline]
+    fn qdir(self, other: Self) -> Self::Output { (other - self).qnorm() }
+}
+impl<T: Num + QRsqrt, const N: usize> QDirection for &Vector<T, N> {
+    type Output = Vector<T, N>;
+    #[inline]
+    fn qdir(self, other: Self) -> Self::Output { (other - self).qnorm() }
+}
+
+/// For 3D vectors, the vector perpendicular to both operands.
+/// For 2D vectors, the "perp-dot" product: the z component of the 3D cross product.
+pub trait CrossProduct {
+    type Output;
+    #[must_use]
+    fn cross(self, other: Self) -> Self::Output;
 }
-impl<const N: usize> QDirection for Vector<f32, N> {
+impl<T: Num> CrossProduct for Vector<T, 3> {
     type Output = Self;
     #[inline]
-    unsafe fn qdir(self, other: Self) -> Self::Output { (other - self).qnorm() }
+    fn cross(self, other: Self) -> Self::Output {
+        let (Vector([ax, ay, az]), Vector([bx, by, bz])) = (self, other);
+        Self([ay * bz - az * by, az * bx - ax * bz, ax * by - ay * bx])
+    }
+}
+impl<T: Num> CrossProduct for &Vector<T, 3> {
+    type Output = Vector<T, 3>;
+    #[inline]
+    fn cross(self, other: Self) -> Self::Output { (*self).cross(*other) }
+}
+impl<T: Num> CrossProduct for Vector<T, 2> {
+    type Output = T;
+    #[inline]
+    fn cross(self, other: Self) -> Self::Output {
+        let (Vector([ax, ay]), Vector([bx, by])) = (self, other);
+        ax * by - ay * bx
+    }
+}
+impl<T: Num> CrossProduct for &Vector<T, 2> {
+    type Output = T;
+    #[inline]
+    fn cross(self, other: Self) -> Self::Output { (*self).cross(*other) }
+}
+
+/// Scalar triple product `a · (b × c)`, the signed volume of the parallelepiped spanned by the operands
+pub trait TripleProduct {
+    type Output;
+    #[must_use]
+    fn triple(self, b: Self, c: Self) -> Self::Output;
 }
-impl<const N: usize> QDirection for &Vector<f32, N> {
-    type Output = Vector<f32, N>;
+impl<T: Num> TripleProduct for Vector<T, 3> {
+    type Output = T;
     #[inline]
-    unsafe fn qdir(self, other: Self) -> Self::Output { (other - self).qnorm() }
+    fn triple(self, b: Self, c: Self) -> Self::Output { self.dot(b.cross(c)) }
+}
+impl<T: Num> TripleProduct for &Vector<T, 3> {
+    type Output = T;
+    #[inline]
+    fn triple(self, b: Self, c: Self) -> Self::Output { (*self).triple(*b, *c) }
 }
 
 // general definition
 impl<T, const N: usize> Vector<T, N> {
-    pub const fn xyz(&self, index: usize) -> &T { &self.0[index] }
-    pub fn xyz_mut(&mut self, index: usize) -> &mut T { &mut self.0[index] }
+    pub const fn comp(&self, index: usize) -> &T { &self.0[index] }
+    pub fn comp_mut(&mut self, index: usize) -> &mut T { &mut self.0[index] }
+
+    pub const fn as_array(&self) -> &[T; N] { &self.0 }
+    pub fn as_array_mut(&mut self) -> &mut [T; N] { &mut self.0 }
+    pub const fn as_slice(&self) -> &[T] { &self.0 }
+    pub fn as_mut_slice(&mut self) -> &mut [T] { &mut self.0 }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> { self.0.iter() }
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> { self.0.iter_mut() }
+
+    /// Convert each component like `as` would
+    pub fn cast<U>(self) -> Vector<U, N> where T: Cast<U> { self.map(T::cast) }
+
+    /// Convert each component, failing if any of them is NaN or out of range for `U`
+    pub fn try_cast<U>(self) -> Result<Vector<U, N>, CastError> where T: TryCast<U> {
+        let results = self.0.map(T::try_cast);
+        if let Some(Err(e)) = results.iter().find(|r| r.is_err()) { return Err(*e); }
+        Ok(Vector(results.map(#[inline] |r| r.unwrap_or_else(|_| unreachable!()))))
+    }
+
+    /// Apply `f` to each component
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Vector<U, N> { Vector(self.0.map(f)) }
+
+    /// Apply `f` to each pair of corresponding components
+    pub fn zip_map<U, V>(self, other: Vector<U, N>, mut f: impl FnMut(T, U) -> V) -> Vector<V, N> {
+        let mut other = other.0.into_iter();
+        Vector(self.0.map(#[inline] |comp| f(comp, other.next().unwrap())))
+    }
+}
+
+// masks
+impl<T: Copy + PartialEq, const N: usize> Vector<T, N> {
+    pub fn cmpeq(self, other: Self) -> BVec<N> { self.zip_map(other, #[inline] |a, b| a == b) }
+    pub fn cmpne(self, other: Self) -> BVec<N> { self.zip_map(other, #[inline] |a, b| a != b) }
+}
+impl<T: Copy + PartialOrd, const N: usize> Vector<T, N> {
+    pub fn cmplt(self, other: Self) -> BVec<N> { self.zip_map(other, #[inline] |a, b| a <  b) }
+    pub fn cmple(self, other: Self) -> BVec<N> { self.zip_map(other, #[inline] |a, b| a <= b) }
+    pub fn cmpgt(self, other: Self) -> BVec<N> { self.zip_map(other, #[inline] |a, b| a >  b) }
+    pub fn cmpge(self, other: Self) -> BVec<N> { self.zip_map(other, #[inline] |a, b| a >= b) }
+}
+impl<T, const N: usize> Vector<T, N> {
+    /// Take each component from `if_true` where `mask` is set, and from `if_false` elsewhere
+    pub fn select(mask: BVec<N>, if_true: Self, if_false: Self) -> Self {
+        let (mut if_true, mut if_false) = (if_true.0.into_iter(), if_false.0.into_iter());
+        mask.map(#[inline] |m| {
+            let (t, f) = (if_true.next().unwrap(), if_false.next().unwrap());
+            if m { t } else { f }
+        })
+    }
+}
+impl<const N: usize> BVec<N> {
+    /// Whether any component is set
+    pub fn any(self) -> bool { self.0.into_iter().any(#[inline] |b| b) }
+    /// Whether every component is set
+    pub fn all(self) -> bool { self.0.into_iter().all(#[inline] |b| b) }
+}
+/// A component-wise boolean mask, as returned by comparisons like [`Vector::cmplt`]
+pub type BVec<const N: usize> = Vector<bool, N>;
+
+// component-wise
+impl<T: Num, const N: usize> Vector<T, N> {
+    #[must_use]
+    pub fn min(self, other: Self) -> Self { self.zip_map(other, #[inline] |a, b| if b < a { b } else { a }) }
+    #[must_use]
+    pub fn max(self, other: Self) -> Self { self.zip_map(other, #[inline] |a, b| if b > a { b } else { a }) }
+    /// Restrict each component to the matching range in `lo..=hi`.
+    /// Unlike [`f32::clamp`] this doesn't panic if `lo > hi`; that component becomes `hi`.
+    #[must_use]
+    pub fn clamp(self, lo: Self, hi: Self) -> Self { self.max(lo).min(hi) }
+
+    /// Smallest component. A vector with no components has none, so this doesn't compile for `N == 0`:
+    ///
+    /// ```compile_fail
+    /// # use amy_math::prelude::*;
+    /// let _ = Vector::<i32, 0>::from([]).min_element();
+    /// ```
+    pub fn min_element(self) -> T {
+        const { assert!(N > 0, "a vector with no components has no smallest component") };
+        self.0.into_iter().reduce(#[inline] |a, b| if b < a { b } else { a }).unwrap()
+    }
+    /// Largest component, which like [`Self::min_element`] doesn't compile for `N == 0`
+    pub fn max_element(self) -> T {
+        const { assert!(N > 0, "a vector with no components has no largest component") };
+        self.0.into_iter().reduce(#[inline] |a, b| if b > a { b } else { a }).unwrap()
+    }
+    /// Sum of the components
+    pub fn sum(self) -> T { self.0.into_iter().fold(T::zero(), #[inline] |acc, comp| acc + comp) }
+    /// Product of the components
+    pub fn product(self) -> T { self.0.into_iter().fold(T::one(), #[inline] |acc, comp| acc * comp) }
+}
+impl<T: Signed, const N: usize> Vector<T, N> {
+    #[must_use]
+    pub fn abs(self) -> Self { self.map(T::abs) }
+    #[must_use]
+    pub fn signum(self) -> Self { self.map(T::signum) }
+}
+impl<T: Real, const N: usize> Vector<T, N> {
+    #[must_use]
+    pub fn floor(self) -> Self { self.map(T::floor) }
+    #[must_use]
+    pub fn ceil(self) -> Self { self.map(T::ceil) }
+    #[must_use]
+    pub fn round(self) -> Self { self.map(T::round) }
+    /// `x - x.trunc()` for each component, so negative components have a negative fractional part
+    #[must_use]
+    pub fn fract(self) -> Self { self.map(T::fract) }
+    /// `self * a + b` with a single rounding per component
+    #[must_use]
+    pub fn mul_add(self, a: Self, b: Self) -> Self { Vector(std::array::from_fn(#[inline] |i| self.0[i].mul_add(a.0[i], b.0[i]))) }
+}
+
+/// Overflow-aware component-wise arithmetic for integer vectors
+macro_rules! impl_int_arith {
+    ($($op:ident),+) => { paste! {
+        impl<T: Integer, const N: usize> Vector<T, N> {$(
+            /// `None` if any component overflows or divides by zero
+            #[must_use]
+            pub fn [<checked_ $op>](self, rhs: Self) -> Option<Self> {
+                let mut out = self.0;
+                for (comp, rhs) in out.iter_mut().zip(rhs.0) { *comp = comp.[<checked_ $op>](rhs)?; }
+                Some(Self(out))
+            }
+            #[must_use]
+            pub fn [<wrapping_ $op>](self, rhs: Self) -> Self { self.zip_map(rhs, T::[<wrapping_ $op>]) }
+            #[must_use]
+            pub fn [<saturating_ $op>](self, rhs: Self) -> Self { self.zip_map(rhs, T::[<saturating_ $op>]) }
+            /// The wrapped result, and whether any component overflowed
+            #[must_use]
+            pub fn [<overflowing_ $op>](self, rhs: Self) -> (Self, bool) {
+                let mut overflow = false;
+                let out = self.zip_map(rhs, #[inline] |a, b| { let (c, o) = a.[<overflowing_ $op>](b); overflow |= o; c });
+                (out, overflow)
+            }
+        )+}
+    }};
+}
+impl_int_arith!(add, sub, mul, div);
+
+/// Generates shader-style swizzles from a list of `(name index)` components.
+///
+/// Reads (`v.zxy()`) may repeat components and return a new [`Vector`] of 2 to 4 components.
+/// Writes (`v.set_zx(...)`) may not repeat components, so they are only generated for permutations.
+macro_rules! impl_swizzles {
+    ($n:literal: $($comp:tt)+) => {
+        impl<T: Copy> Vector<T, $n> {
+            impl_swizzles!(@read [] [$($comp)+] [$($comp)+]);
+            impl_swizzles!(@write [] [] [$($comp)+]);
+        }
+    };
+
+    // every sequence of up to 4 components
+    (@read [$($chosen:tt)*] [] $all:tt) => {};
+    (@read [$c0:tt $c1:tt $c2:tt] [$cur:tt $($rest:tt)*] $all:tt) => {
+        impl_swizzles!(@get $c0 $c1 $c2 $cur);
+        impl_swizzles!(@read [$c0 $c1 $c2] [$($rest)*] $all);
+    };
+    (@read [$($chosen:tt)*] [$cur:tt $($rest:tt)*] $all:tt) => {
+        impl_swizzles!(@get $($chosen)* $cur);
+        impl_swizzles!(@read [$($chosen)* $cur] $all $all);
+        impl_swizzles!(@read [$($chosen)*] [$($rest)*] $all);
+    };
+
+    (@get $single:tt) => {};
+    (@get ($c0:ident $i0:tt) $(($c:ident $i:tt))+) => {
+        paste! {
+            #[must_use]
+            pub fn [<$c0 $($c)+>](&self) -> Vector<T, { [$i0 $(, $i)+].len() }> { Vector([self.0[$i0] $(, self.0[$i])+]) }
+        }
+    };
+
+    // every sequence of up to N distinct components
+    (@write [$($chosen:tt)*] [$($before:tt)*] []) => {};
+    (@write [$($chosen:tt)*] [$($before:tt)*] [$cur:tt $($after:tt)*]) => {
+        impl_swizzles!(@set $($chosen)* $cur);
+        impl_swizzles!(@write [$($chosen)* $cur] [] [$($before)* $($after)*]);
+        impl_swizzles!(@write [$($chosen)*] [$($before)* $cur] [$($after)*]);
+    };
+
+    (@set $single:tt) => {};
+    (@set ($c0:ident $i0:tt) $(($c:ident $i:tt))+) => {
+        paste! {
+            pub fn [<set_ $c0 $($c)+>](&mut self, value: Vector<T, { [$i0 $(, $i)+].len() }>) {
+                let Vector([$c0 $(, $c)+]) = value;
+                self.0[$i0] = $c0;
+                $(self.0[$i] = $c;)+
+            }
+        }
+    };
 }
 
 // 2D
@@ -259,9 +581,16 @@ impl<T> Vector<T, 2> {
     pub fn x_mut(&mut self) -> &mut T { &mut self.0[0] }
     pub fn y_mut(&mut self) -> &mut T { &mut self.0[1] }
 }
+impl_swizzles!(2: (x 0) (y 1));
 pub type  Vec2 = Vector<f32, 2>;
 pub type IVec2 = Vector<i32, 2>;
 pub type UVec2 = Vector<u32, 2>;
+pub type DVec2 = Vector<f64, 2>;
+pub type I64Vec2 = Vector<i64, 2>;
+pub type U64Vec2 = Vector<u64, 2>;
+pub type I16Vec2 = Vector<i16, 2>;
+pub type U8Vec2 = Vector<u8, 2>;
+pub type BVec2 = Vector<bool, 2>;
 
 // 3D
 impl<T> From<Vector<T, 3>> for (T, T, T) { fn from(Vector([x, y, z]): Vector<T, 3>) -> Self { (x, y, z) } }
@@ -277,9 +606,46 @@ impl<T> Vector<T, 3> {
     pub fn y_mut(&mut self) -> &mut T { &mut self.0[1] }
     pub fn z_mut(&mut self) -> &mut T { &mut self.0[2] }
 }
+impl<T: Signed> Vector<T, 3> {
+    /// Some vector perpendicular to `self`, not normalized
+    #[must_use]
+    pub fn any_orthogonal(self) -> Self {
+        let Vector([x, y, z]) = self;
+        if x.abs() > z.abs() { Self([-y, x, T::zero()]) } else { Self([T::zero(), -z, y]) }
+    }
+}
+impl<T: Float> Vector<T, 3> {
+    /// Some unit vector perpendicular to `self`, which must be normalized
+    #[must_use]
+    pub fn any_orthonormal(self) -> Self {
+        self.orthonormal_basis().0
+    }
+
+    /// Two unit vectors which together with `self` (which must be normalized) form a right-handed orthonormal basis
+    ///
+    /// Duff et al., "Building an Orthonormal Basis, Revisited"
+    pub fn orthonormal_basis(self) -> (Self, Self) {
+        let Vector([x, y, z]) = self;
+        let sign = T::one().copysign(z);
+        let a = -(sign + z).recip();
+        let b = x * y * a;
+        (
+            Self([T::one() + sign * x * x * a, sign * b, -sign * x]),
+            Self([b, sign + y * y * a, -y]),
+        )
+    }
+}
+
+impl_swizzles!(3: (x 0) (y 1) (z 2));
 pub type  Vec3 = Vector<f32, 3>;
 pub type IVec3 = Vector<i32, 3>;
 pub type UVec3 = Vector<u32, 3>;
+pub type DVec3 = Vector<f64, 3>;
+pub type I64Vec3 = Vector<i64, 3>;
+pub type U64Vec3 = Vector<u64, 3>;
+pub type I16Vec3 = Vector<i16, 3>;
+pub type U8Vec3 = Vector<u8, 3>;
+pub type BVec3 = Vector<bool, 3>;
 
 // 4D
 impl<T> From<Vector<T, 4>> for (T, T, T, T) { fn from(Vector([x, y, z, w]): Vector<T, 4>) -> Self { (x, y, z, w) } }
@@ -297,9 +663,16 @@ impl<T> Vector<T, 4> {
     pub fn z_mut(&mut self) -> &mut T { &mut self.0[2] }
     pub fn w_mut(&mut self) -> &mut T { &mut self.0[3] }
 }
+impl_swizzles!(4: (x 0) (y 1) (z 2) (w 3));
 pub type  Vec4 = Vector<f32, 4>;
 pub type IVec4 = Vector<i32, 4>;
 pub type UVec4 = Vector<u32, 4>;
+pub type DVec4 = Vector<f64, 4>;
+pub type I64Vec4 = Vector<i64, 4>;
+pub type U64Vec4 = Vector<u64, 4>;
+pub type I16Vec4 = Vector<i16, 4>;
+pub type U8Vec4 = Vector<u8, 4>;
+pub type BVec4 = Vector<bool, 4>;
 
 #[cfg(test)]
 mod tests {
@@ -310,4 +683,346 @@ mod tests {
         let v = IVec2::new(6, 2) / 2;
         assert_eq!(v, IVec2::new(3, 1));
     }
+
+    #[test]
+    fn test_cross() {
+        let (x, y, z) = (IVec3::new(1, 0, 0), IVec3::new(0, 1, 0), IVec3::new(0, 0, 1));
+        assert_eq!(x.cross(y), z);
+        assert_eq!(y.cross(x), -z);
+        assert_eq!(x.triple(y, z), 1);
+        assert_eq!(IVec2::new(2, 0).cross(IVec2::new(0, 3)), 6);
+
+        for n in [Vec3::new(0.0, 0.0, 1.0), Vec3::new(0.0, 0.0, -1.0), Vec3::new(1.0, 2.0, -3.0).norm()] {
+            let (b1, b2) = n.orthonormal_basis();
+            assert!(b1.dot(n).abs() < 1e-6 && b2.dot(n).abs() < 1e-6 && b1.dot(b2).abs() < 1e-6);
+            assert!((b1.cross(b2) - n).len() < 1e-6);
+            assert!(n.any_orthogonal().dot(n).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_orthogonal_edge_cases() {
+        let axes = [IVec3::new(1, 0, 0), IVec3::new(0, 1, 0), IVec3::new(0, 0, 1)];
+        for n in axes.into_iter().flat_map(|axis| [axis, -axis]) {
+            let o = n.any_orthogonal();
+            assert!(o.dot(n) == 0 && !o.is_zero(), "{n:?}");
+        }
+
+        // the sign flip at z = 0 and the pole at z = -1 are where the basis construction can break down
+        let near_pole = Vec3::new(1e-4, -2e-4, -1.0).norm();
+        for n in axes.into_iter().flat_map(|axis| [axis, -axis]).map(|axis| axis.cast::<f32>()).chain([near_pole, Vec3::new(0.6, 0.0, -0.8)]) {
+            let (b1, b2) = n.orthonormal_basis();
+            assert!((b1.len() - 1.0).abs() < 1e-6 && (b2.len() - 1.0).abs() < 1e-6, "{n:?}");
+            assert!(b1.dot(n).abs() < 1e-6 && b2.dot(n).abs() < 1e-6 && b1.dot(b2).abs() < 1e-6, "{n:?}");
+            assert!((b1.cross(b2) - n).len() < 1e-6, "{n:?}");
+            assert!((n.any_orthonormal().len() - 1.0).abs() < 1e-6, "{n:?}");
+        }
+    }
+
+    #[test]
+    fn test_assign_ops() {
+        let mut v = IVec3::new(6, 9, 12);
+        v += IVec3::new(1, 1, 1);
+        v -= 1;
+        v *= IVec3::new(1, 2, 3);
+        v /= 3;
+        assert_eq!(v, IVec3::new(2, 6, 12));
+        v %= 5;
+        assert_eq!(v, IVec3::new(2, 1, 2));
+        v <<= 2;
+        v |= IVec3::new(1, 0, 0);
+        assert_eq!(v, IVec3::new(9, 4, 8));
+        assert_eq!(!UVec2::new(0, u32::MAX) & 0xF, UVec2::new(0xF, 0));
+        assert_eq!(IVec2::new(7, -7) % IVec2::new(4, 4), IVec2::new(3, -3));
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn test_div_assign_by_zero() {
+        let mut v = IVec3::new(1, 2, 3);
+        v /= IVec3::new(1, 0, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn test_div_assign_by_scalar_zero() {
+        let mut v = UVec2::new(1, 2);
+        v /= 0;
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide with overflow")]
+    fn test_div_by_scalar_overflow() {
+        assert_eq!(IVec2::new(i32::MIN, 7) / -2, IVec2::new(1 << 30, -3));
+        let _ = IVec2::new(i32::MIN, 1) / -1;
+    }
+
+    #[test]
+    fn test_zero_one() {
+        assert_eq!(Vec3::zero(), Vec3::new(0.0, 0.0, 0.0));
+        assert_eq!(UVec2::one(), UVec2::new(1, 1));
+        assert_eq!(IVec4::default(), IVec4::zero());
+        assert!(Vec2::zero().is_zero() && !Vec2::one().is_zero());
+        assert_eq!(Vector::<f64, 2>::new(3.0, 4.0).len(), 5.0);
+    }
+
+    #[test]
+    fn test_qnorm() {
+        assert!((Vec3::new(1.0, 2.0, 2.0).qnorm().len() - 1.0).abs() < 2e-3);
+        assert!((Vector::<f64, 3>::new(1.0, 2.0, 2.0).qnorm().len() - 1.0).abs() < 2e-3);
+        assert!((Vec2::new(1.0, 1.0).qdir(Vec2::new(4.0, 5.0)) - Vec2::new(0.6, 0.8)).len() < 2e-3);
+    }
+
+    #[test]
+    fn test_component_wise() {
+        let (a, b) = (IVec3::new(1, -5, 3), IVec3::new(2, -6, 3));
+        assert_eq!(a.min(b), IVec3::new(1, -6, 3));
+        assert_eq!(a.max(b), IVec3::new(2, -5, 3));
+        assert_eq!(a.clamp(IVec3::new(0, 0, 0), IVec3::new(2, 2, 2)), IVec3::new(1, 0, 2));
+        assert_eq!(a.abs(), IVec3::new(1, 5, 3));
+        assert_eq!(a.signum(), IVec3::new(1, -1, 1));
+        assert_eq!((a.min_element(), a.max_element(), a.sum(), a.product()), (-5, 3, -1, -15));
+
+        let v = Vec3::new(1.25, -1.75, 2.5);
+        assert_eq!(v.floor(), Vec3::new(1.0, -2.0, 2.0));
+        assert_eq!(v.ceil(), Vec3::new(2.0, -1.0, 3.0));
+        assert_eq!(v.round(), Vec3::new(1.0, -2.0, 3.0));
+        assert_eq!(v.fract(), Vec3::new(0.25, -0.75, 0.5));
+        assert_eq!(v.mul_add(Vec3::one() * 2.0, Vec3::one()), Vec3::new(3.5, -2.5, 6.0));
+
+        assert_eq!(a.map(|x| x as f32 * 0.5), Vec3::new(0.5, -2.5, 1.5));
+        assert_eq!(a.zip_map(v, |i, f| i as f32 + f), Vec3::new(2.25, -6.75, 5.5));
+    }
+
+    #[test]
+    fn test_component_wise_edge_cases() {
+        // an inverted range doesn't panic, the upper bound wins
+        assert_eq!(IVec3::new(-5, 0, 5).clamp(IVec3::new(2, 2, 2), IVec3::new(1, 1, 1)), IVec3::new(1, 1, 1));
+        assert_eq!(Vec2::new(0.5, 9.0).clamp(Vec2::new(0.0, 3.0), Vec2::new(1.0, 2.0)), Vec2::new(0.5, 2.0));
+        assert_eq!(Vec2::new(3.0, -3.0).clamp(Vec2::one(), Vec2::one()), Vec2::one());
+        assert_eq!((Vector([-7]).min_element(), Vector([-7]).max_element()), (-7, -7));
+
+        let f = Vec4::new(-0.25, -2.0, -3.5, -1e-3).fract();
+        assert_eq!(f, Vec4::new(-0.25, 0.0, -0.5, -1e-3));
+        assert_eq!(DVec2::new(-1e16, 1e16).fract(), DVec2::zero());
+    }
+
+    #[test]
+    fn test_access() {
+        let mut v = IVec3::new(1, 2, 3);
+        v[1] = 5;
+        assert_eq!((v[0], v[1], v[2]), (1, 5, 3));
+        for comp in &mut v { *comp *= 2; }
+        assert_eq!(v.iter().copied().collect::<Vec<_>>(), [2, 10, 6]);
+        assert_eq!(v.as_slice(), &[2, 10, 6]);
+        v.as_array_mut()[2] = 0;
+        assert_eq!((&v).into_iter().max(), Some(&10));
+
+        let points = [Vec3::new(1.0, 0.0, 2.0), Vec3::new(3.0, 4.0, 0.0)];
+        assert_eq!(points.iter().sum::<Vec3>() / 2.0, Vec3::new(2.0, 2.0, 1.0));
+        assert_eq!(points.into_iter().product::<Vec3>(), Vec3::new(3.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_access_edge_cases() {
+        assert_eq!(std::iter::empty::<IVec3>().sum::<IVec3>(), IVec3::zero());
+        assert_eq!(std::iter::empty::<IVec3>().product::<IVec3>(), IVec3::one());
+        assert_eq!([IVec2::new(1, 2)].iter().sum::<IVec2>(), IVec2::new(1, 2));
+        assert_eq!(Vector::<i32, 0>::default().iter().count(), 0);
+        assert!(IVec2::new(1, 2).as_slice().get(2).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn test_index_out_of_bounds() {
+        let v = IVec3::new(1, 2, 3);
+        let _ = v[3];
+    }
+
+    #[test]
+    fn test_display_parse() {
+        assert_eq!(Vec3::new(1.0, -2.5, 3.0).to_string(), "(1, -2.5, 3)");
+        assert_eq!(format!("{:.2}", Vec2::new(1.0, 0.125)), "(1.00, 0.12)");
+        assert_eq!(format!("{:>3}", IVec2::new(1, 20)), "(  1,  20)");
+
+        assert_eq!("(1, 2, 3)".parse(), Ok(IVec3::new(1, 2, 3)));
+        assert_eq!("[1,2,3]".parse(), Ok(IVec3::new(1, 2, 3)));
+        assert_eq!(" 1 2  3 ".parse(), Ok(IVec3::new(1, 2, 3)));
+        assert_eq!("1.5, -2".parse(), Ok(Vec2::new(1.5, -2.0)));
+        let v = Vec3::new(0.1, 1e-9, -7.0);
+        assert_eq!(v.to_string().parse(), Ok(v));
+
+        assert_eq!("(1, 2)".parse::<IVec3>(), Err(ParseVectorError::WrongArity { expected: 3, found: 2 }));
+        assert_eq!("".parse::<IVec2>(), Err(ParseVectorError::WrongArity { expected: 2, found: 0 }));
+        assert!(matches!("(1, x, 3)".parse::<IVec3>(), Err(ParseVectorError::Component { index: 1, .. })));
+        assert!(matches!("(1 2 3]".parse::<IVec3>(), Err(ParseVectorError::Component { index: 0, .. })));
+        assert_eq!("1 x".parse::<IVec2>().unwrap_err().to_string(), "invalid component 1: invalid digit found in string");
+    }
+
+    #[test]
+    fn test_parse_edge_cases() {
+        // a bracket only counts when it has a matching partner, otherwise it ends up in a component
+        for s in ["(1, 2]", "[1, 2)", "(1, 2", "1, 2)", "((1, 2))"] {
+            assert!(matches!(s.parse::<IVec2>(), Err(ParseVectorError::Component { .. })), "{s}");
+        }
+        assert_eq!("(1, 2, 3)".parse::<IVec2>(), Err(ParseVectorError::WrongArity { expected: 2, found: 3 }));
+        assert_eq!("(1, 2,)".parse::<IVec2>(), Err(ParseVectorError::WrongArity { expected: 2, found: 3 }));
+        assert_eq!("1,,2".parse::<IVec2>(), Err(ParseVectorError::WrongArity { expected: 2, found: 3 }));
+        assert_eq!("()".parse::<IVec2>(), Err(ParseVectorError::WrongArity { expected: 2, found: 0 }));
+
+        assert_eq!("".parse::<Vector<i32, 0>>(), Ok(Vector([])));
+        assert_eq!(" ( ) ".parse::<Vector<i32, 0>>(), Ok(Vector([])));
+        assert_eq!("[1]".parse::<Vector<i32, 0>>(), Err(ParseVectorError::WrongArity { expected: 0, found: 1 }));
+        assert_eq!(Vector::<i32, 0>([]).to_string().parse::<Vector<i32, 0>>(), Ok(Vector([])));
+    }
+
+    #[test]
+    fn test_int_arith() {
+        use std::num::{Saturating, Wrapping};
+        let a = UVec2::new(u32::MAX - 1, 5);
+        assert_eq!(a.checked_add(UVec2::new(1, 1)), Some(UVec2::new(u32::MAX, 6)));
+        assert_eq!(a.checked_add(UVec2::new(2, 1)), None);
+        assert_eq!(a.checked_div(UVec2::new(2, 0)), None);
+        assert_eq!(a.wrapping_add(UVec2::new(3, 1)), UVec2::new(1, 6));
+        assert_eq!(a.saturating_mul(UVec2::new(2, 2)), UVec2::new(u32::MAX, 10));
+        assert_eq!(IVec2::new(i32::MIN, 4).saturating_sub(IVec2::new(1, 5)), IVec2::new(i32::MIN, -1));
+        assert_eq!(IVec2::new(i32::MIN, 4).overflowing_div(IVec2::new(-1, 2)), (IVec2::new(i32::MIN, 2), true));
+        assert_eq!(a.overflowing_sub(UVec2::new(1, 5)), (UVec2::new(u32::MAX - 2, 0), false));
+
+        let w = Vector([Wrapping(u32::MAX), Wrapping(0)]) + Vector([Wrapping(2), Wrapping(1)]);
+        assert_eq!(w, Vector([Wrapping(1), Wrapping(1)]));
+        assert_eq!(w.sum(), Wrapping(2));
+        let s = Vector([Saturating(u32::MAX - 1), Saturating(3)]) * Saturating(2);
+        assert_eq!(s, Vector([Saturating(u32::MAX), Saturating(6)]));
+        assert_eq!(s / Saturating(2), Vector([Saturating(u32::MAX / 2), Saturating(3)]));
+    }
+
+    #[test]
+    fn test_int_arith_signed_bounds() {
+        let (min, max) = (IVec2::new(i32::MIN, 0), IVec2::new(i32::MAX, 0));
+        let (one, neg_one) = (IVec2::new(1, 1), IVec2::new(-1, -1));
+
+        assert_eq!(max.checked_add(IVec2::new(0, 1)), Some(IVec2::new(i32::MAX, 1)));
+        assert_eq!(max.checked_add(one), None);
+        assert_eq!(min.checked_sub(one), None);
+        assert_eq!(min.checked_sub(neg_one), Some(IVec2::new(i32::MIN + 1, 1)));
+        assert_eq!(min.checked_mul(neg_one), None);
+        assert_eq!(max.checked_mul(neg_one), Some(IVec2::new(-i32::MAX, 0)));
+        assert_eq!(min.checked_div(neg_one), None);
+        assert_eq!(min.checked_div(one), Some(min));
+        assert_eq!(I64Vec2::new(i64::MIN, 1).checked_div(I64Vec2::new(1, -1)), Some(I64Vec2::new(i64::MIN, -1)));
+
+        assert_eq!(max.overflowing_add(one), (IVec2::new(i32::MIN, 1), true));
+        assert_eq!(min.overflowing_sub(one), (IVec2::new(i32::MAX, -1), true));
+        assert_eq!(min.overflowing_mul(neg_one), (IVec2::new(i32::MIN, 0), true));
+        assert_eq!(min.overflowing_div(neg_one), (IVec2::new(i32::MIN, 0), true));
+        // the flag is set if any component overflows, whichever it is
+        assert_eq!(IVec2::new(0, i32::MAX).overflowing_add(one), (IVec2::new(1, i32::MIN), true));
+        assert_eq!(max.overflowing_sub(one), (IVec2::new(i32::MAX - 1, -1), false));
+        assert_eq!(min.overflowing_div(one), (min, false));
+
+        assert_eq!(min.saturating_div(neg_one), IVec2::new(i32::MAX, 0));
+        assert_eq!(min.saturating_mul(neg_one), IVec2::new(i32::MAX, 0));
+        assert_eq!(min.wrapping_div(neg_one), min);
+    }
+
+    #[test]
+    fn test_cast() {
+        assert_eq!(IVec2::new(-3, 7).cast::<f32>(), Vec2::new(-3.0, 7.0));
+        assert_eq!(Vec3::new(1.9, -1.9, 300.0).cast::<u8>(), U8Vec3::new(1, 0, 255));
+        assert_eq!(Vec3::new(1.9, 2.0, 255.5).try_cast::<u8>(), Ok(U8Vec3::new(1, 2, 255)));
+        assert_eq!(Vec3::new(1.0, 256.0, 0.0).try_cast::<u8>(), Err(CastError::OutOfRange));
+        assert_eq!(Vec2::new(f32::NAN, 0.0).try_cast::<i32>(), Err(CastError::NaN));
+        assert_eq!(IVec2::new(-1, 0).try_cast::<u32>(), Err(CastError::OutOfRange));
+        assert_eq!(Vec2::new(4294967040.0, 0.0).try_cast::<u32>(), Ok(UVec2::new(4294967040, 0)));
+        assert_eq!(Vec2::new(4294967296.0, 0.0).try_cast::<u32>(), Err(CastError::OutOfRange));
+        assert_eq!(DVec2::new(1e300, 0.0).try_cast::<f32>(), Err(CastError::OutOfRange));
+        assert_eq!(BVec2::new(true, false).cast::<i32>(), IVec2::new(1, 0));
+
+        assert_eq!(DVec3::from(IVec3::new(1, 2, 3)), DVec3::new(1.0, 2.0, 3.0));
+        assert_eq!(I64Vec2::from(I16Vec2::new(-4, 4)), I64Vec2::new(-4, 4));
+        assert_eq!(UVec4::from(U8Vec4::new(1, 2, 3, 4)), UVec4::new(1, 2, 3, 4));
+    }
+
+    #[test]
+    fn test_cast_edge_cases() {
+        // `as` saturates floats and maps NaN to zero, and wraps integers
+        assert_eq!(Vec4::new(f32::NAN, f32::INFINITY, f32::NEG_INFINITY, -0.9).cast::<i32>(), IVec4::new(0, i32::MAX, i32::MIN, 0));
+        assert_eq!(I64Vec2::new(40000, -1).cast::<i16>(), I16Vec2::new(-25536, -1));
+        assert_eq!(IVec2::new(-1, 256).cast::<u8>(), U8Vec2::new(255, 0));
+
+        // exact bounds of the target type
+        assert_eq!(Vec2::new(-2147483648.0, -0.9).try_cast::<i32>(), Ok(IVec2::new(i32::MIN, 0)));
+        assert_eq!(Vec2::new(2147483648.0, 0.0).try_cast::<i32>(), Err(CastError::OutOfRange));
+        assert_eq!(Vec2::new(-2147483904.0, 0.0).try_cast::<i32>(), Err(CastError::OutOfRange));
+        assert_eq!(DVec2::new(9223372036854774784.0, -9223372036854775808.0).try_cast::<i64>(), Ok(I64Vec2::new(9223372036854774784, i64::MIN)));
+        assert_eq!(DVec2::new(9223372036854775808.0, 0.0).try_cast::<i64>(), Err(CastError::OutOfRange));
+        assert_eq!(Vec2::new(f32::INFINITY, 0.0).try_cast::<u32>(), Err(CastError::OutOfRange));
+        assert_eq!(I64Vec2::new(i16::MIN as i64, 40000).try_cast::<i16>(), Err(CastError::OutOfRange));
+        assert_eq!(I64Vec2::new(i16::MIN as i64, i16::MAX as i64).try_cast::<i16>(), Ok(I16Vec2::new(i16::MIN, i16::MAX)));
+
+        // infinities and NaN are representable in floats, only finite overflow fails
+        let v = DVec2::new(f64::INFINITY, f64::NAN).try_cast::<f32>().unwrap();
+        assert!(v[0] == f32::INFINITY && v[1].is_nan());
+        assert_eq!(DVec2::new(f32::MAX as f64, 0.0).try_cast::<f32>(), Ok(Vec2::new(f32::MAX, 0.0)));
+        // the first error wins
+        assert_eq!(Vec2::new(f32::NAN, 1e20).try_cast::<i32>(), Err(CastError::NaN));
+        assert_eq!(Vec2::new(1e20, f32::NAN).try_cast::<i32>(), Err(CastError::OutOfRange));
+    }
+
+    #[test]
+    fn test_mask() {
+        let (a, b) = (IVec3::new(1, 5, 3), IVec3::new(2, 5, 1));
+        assert_eq!(a.cmplt(b), BVec3::new(true, false, false));
+        assert_eq!(a.cmple(b), BVec3::new(true, true, false));
+        assert_eq!(a.cmpeq(b) | a.cmpgt(b), a.cmpge(b));
+        assert_eq!(!a.cmpne(b), a.cmpeq(b));
+        assert_eq!(IVec3::select(a.cmplt(b), a, b), a.min(b));
+        assert!(a.cmpne(b).any() && !a.cmpne(b).all());
+
+        // branchless point-in-AABB
+        let (lo, hi) = (Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 2.0, 3.0));
+        let inside = |p: Vec3| (p.cmpge(lo) & p.cmple(hi)).all();
+        assert!(inside(Vec3::new(0.5, 2.0, 0.0)));
+        assert!(!inside(Vec3::new(0.5, 2.5, 0.0)));
+    }
+
+    #[test]
+    fn test_mask_edge_cases() {
+        let (t, f) = (BVec3::new(true, true, true), BVec3::new(false, false, false));
+        assert!(t.all() && t.any() && !f.all() && !f.any());
+        assert!(BVec3::new(false, false, true).any() && !BVec3::new(true, true, false).all());
+        // vacuous truth, like the iterator methods
+        assert!(Vector::<bool, 0>([]).all() && !Vector::<bool, 0>([]).any());
+
+        let (a, b) = (IVec3::new(1, 2, 3), IVec3::new(4, 5, 6));
+        assert_eq!(IVec3::select(t, a, b), a);
+        assert_eq!(IVec3::select(f, a, b), b);
+        assert_eq!(IVec3::select(BVec3::new(false, true, false), a, b), IVec3::new(4, 2, 6));
+        let names = Vector::select(BVec2::new(true, false), Vector([String::from("a"), String::from("b")]), Vector([String::from("c"), String::from("d")]));
+        assert_eq!(names, Vector([String::from("a"), String::from("d")]));
+
+        // NaN compares unequal to everything, itself included
+        let (n, one) = (Vec2::new(f32::NAN, 1.0), Vec2::one());
+        assert_eq!(n.cmpeq(n), BVec2::new(false, true));
+        assert_eq!(n.cmpne(one), BVec2::new(true, false));
+        assert!(!(n.cmplt(one) | n.cmpge(one)).all());
+    }
+
+    #[test]
+    fn test_swizzle() {
+        let v = IVec4::new(1, 2, 3, 4);
+        assert_eq!(v.wzyx(), IVec4::new(4, 3, 2, 1));
+        assert_eq!(v.xzy(), IVec3::new(1, 3, 2));
+        assert_eq!(v.wwxy(), IVec4::new(4, 4, 1, 2));
+        assert_eq!(IVec2::new(5, 6).yyx(), IVec3::new(6, 6, 5));
+        assert_eq!(v.xyz(), IVec3::new(1, 2, 3));
+        assert_eq!(IVec3::new(7, 8, 9).xyz(), IVec3::new(7, 8, 9));
+
+        let mut v = IVec3::new(1, 2, 3);
+        v.set_zx(IVec2::new(7, 8));
+        assert_eq!(v, IVec3::new(8, 2, 7));
+        v.set_yzx(IVec3::new(4, 5, 6));
+        assert_eq!(v, IVec3::new(6, 4, 5));
+    }
 }